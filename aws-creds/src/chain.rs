@@ -0,0 +1,254 @@
+use crate::credentials::Credentials;
+use crate::error::CredentialsError;
+use crate::provider::{CredentialsProvider, ProvideCredentials};
+use log::debug;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// A blocking credentials lookup, such as [`Credentials::from_env`].
+pub(crate) type Lookup = Arc<dyn Fn() -> Result<Credentials, CredentialsError> + Send + Sync>;
+
+/// The lookups of [`Credentials::new`], in order: STS web identity,
+/// environment, profile, then container or instance metadata.
+#[cfg(feature = "http-credentials")]
+pub(crate) fn default_lookups(profile: Option<String>) -> Vec<(&'static str, Lookup)> {
+    vec![
+        (
+            "web-identity",
+            Arc::new(|| Credentials::from_sts_env("aws-creds")),
+        ),
+        ("environment", Arc::new(Credentials::from_env)),
+        (
+            "profile",
+            Arc::new(move || Credentials::from_profile(profile.as_deref())),
+        ),
+        (
+            "instance-metadata",
+            Arc::new(Credentials::from_instance_metadata),
+        ),
+    ]
+}
+
+/// Runs a blocking lookup on the blocking thread pool.
+///
+/// ```
+/// use awscreds::{BlockingProvider, Credentials};
+///
+/// let provider = BlockingProvider::new(|| Credentials::from_env_specific(
+///     Some("MY_ACCESS_KEY"),
+///     Some("MY_SECRET_KEY"),
+///     None,
+///     None,
+/// ));
+/// ```
+#[derive(Clone)]
+pub struct BlockingProvider {
+    lookup: Lookup,
+}
+
+impl BlockingProvider {
+    pub fn new(
+        lookup: impl Fn() -> Result<Credentials, CredentialsError> + Send + Sync + 'static,
+    ) -> Self {
+        BlockingProvider {
+            lookup: Arc::new(lookup),
+        }
+    }
+}
+
+impl fmt::Debug for BlockingProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingProvider").finish_non_exhaustive()
+    }
+}
+
+impl CredentialsProvider for BlockingProvider {
+    fn provide_credentials(&self) -> ProvideCredentials<'_> {
+        let lookup = self.lookup.clone();
+        Box::pin(async move { tokio::task::spawn_blocking(move || lookup()).await? })
+    }
+}
+
+/// Credentials and the name of the provider that supplied them.
+#[derive(Clone, Debug)]
+pub struct ResolvedCredentials {
+    pub credentials: Credentials,
+    pub provider: String,
+}
+
+/// Asks named providers for credentials in order, until one supplies them.
+///
+/// If none does, the error is a [`CredentialsError::Chain`] with the error of
+/// every provider.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> Result<(), awscreds::error::CredentialsError> {
+/// use awscreds::{
+///     AssumeRole, AssumeRoleProvider, BlockingProvider, Credentials, ProviderChain,
+/// };
+///
+/// let chain = ProviderChain::new()
+///     .with_provider("environment", BlockingProvider::new(Credentials::from_env))
+///     .with_provider(
+///         "deploy-role",
+///         AssumeRoleProvider::from_source_profile(
+///             AssumeRole::new("arn:aws:iam::123456789012:role/deploy", "deploy"),
+///             "default",
+///         )?,
+///     );
+/// let resolved = chain.resolve().await?;
+/// println!("credentials from {}", resolved.provider);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ProviderChain {
+    providers: Vec<(String, Box<dyn CredentialsProvider>)>,
+    last_provider: Mutex<Option<String>>,
+}
+
+impl ProviderChain {
+    /// An empty chain, add providers with [`ProviderChain::with_provider`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The chain of [`Credentials::new`], reading the `profile` section of the
+    /// AWS config files, each lookup run on the blocking thread pool.
+    #[cfg(feature = "http-credentials")]
+    pub fn default_chain(profile: Option<&str>) -> Self {
+        default_lookups(profile.map(String::from))
+            .into_iter()
+            .fold(Self::new(), |chain, (name, lookup)| {
+                chain.with_provider(name, BlockingProvider { lookup })
+            })
+    }
+
+    /// Ask `provider` after the providers already in the chain.
+    pub fn with_provider(
+        mut self,
+        name: impl Into<String>,
+        provider: impl CredentialsProvider + 'static,
+    ) -> Self {
+        self.providers.push((name.into(), Box::new(provider)));
+        self
+    }
+
+    /// The names of the providers, in order.
+    pub fn providers(&self) -> impl Iterator<Item = &str> {
+        self.providers.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// The provider that supplied the credentials last time.
+    pub fn last_provider(&self) -> Option<String> {
+        self.last_provider
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub async fn resolve(&self) -> Result<ResolvedCredentials, CredentialsError> {
+        let mut errors = Vec::new();
+        for (name, provider) in &self.providers {
+            match provider.provide_credentials().await {
+                Ok(credentials) => {
+                    debug!("Credentials from {}", name);
+                    *self.last_provider.lock().unwrap_or_else(|e| e.into_inner()) =
+                        Some(name.clone());
+                    return Ok(ResolvedCredentials {
+                        credentials,
+                        provider: name.clone(),
+                    });
+                }
+                Err(error) => {
+                    debug!("No credentials from {}: {}", name, error);
+                    errors.push((name.clone(), error));
+                }
+            }
+        }
+        Err(CredentialsError::Chain(errors))
+    }
+}
+
+impl CredentialsProvider for ProviderChain {
+    fn provide_credentials(&self) -> ProvideCredentials<'_> {
+        Box::pin(async move { Ok(self.resolve().await?.credentials) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct Failing {
+        calls: AtomicUsize,
+    }
+
+    impl CredentialsProvider for Failing {
+        fn provide_credentials(&self) -> ProvideCredentials<'_> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Err(CredentialsError::ConfigNotFound) })
+        }
+    }
+
+    fn fixed() -> Credentials {
+        Credentials::from_env_specific(Some("PATH"), Some("PATH"), None, None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn reports_the_winning_provider() {
+        let failing = Arc::new(Failing::default());
+        let unused = Arc::new(Failing::default());
+        let chain = ProviderChain::new()
+            .with_provider("failing", failing.clone())
+            .with_provider("fixed", fixed())
+            .with_provider("unused", unused.clone());
+        assert_eq!(
+            chain.providers().collect::<Vec<_>>(),
+            ["failing", "fixed", "unused"]
+        );
+        assert_eq!(chain.last_provider(), None);
+
+        let resolved = chain.resolve().await.unwrap();
+        assert_eq!(resolved.provider, "fixed");
+        assert_eq!(resolved.credentials, fixed());
+        assert_eq!(chain.last_provider().as_deref(), Some("fixed"));
+        assert_eq!(failing.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(unused.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn keeps_every_error() {
+        let chain = ProviderChain::new()
+            .with_provider("profile", Failing::default())
+            .with_provider(
+                "environment",
+                BlockingProvider::new(|| {
+                    Credentials::from_env_specific(Some("AWSCREDS_UNSET"), None, None, None)
+                }),
+            );
+
+        let error = chain.resolve().await.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No credentials from profile: Config not found, \
+             environment: Neither AWSCREDS_UNSET, nor AWS_ACCESS_KEY_ID exists in the environment"
+        );
+        match error {
+            CredentialsError::Chain(errors) => {
+                assert!(
+                    matches!(errors[0], (ref name, CredentialsError::ConfigNotFound) if name == "profile")
+                );
+                assert!(
+                    matches!(errors[1], (ref name, CredentialsError::MissingEnvVar(..)) if name == "environment")
+                );
+            }
+            error => panic!("unexpected {}", error),
+        }
+        assert_eq!(chain.last_provider(), None);
+    }
+}
@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 
 use crate::error::CredentialsError;
-use ini::Ini;
+use crate::profile::Profile;
 use log::debug;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -119,6 +119,22 @@ pub struct AssumeRoleWithWebIdentityResult {
     pub provider: String,
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct AssumeRoleResponse {
+    pub assume_role_result: AssumeRoleResult,
+    pub response_metadata: ResponseMetadata,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct AssumeRoleResult {
+    pub assumed_role_user: AssumedRoleUser,
+    pub credentials: StsResponseCredentials,
+    pub packed_policy_size: Option<u32>,
+    pub source_identity: Option<String>,
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StsResponseCredentials {
@@ -187,7 +203,36 @@ fn http_get(url: &str) -> attohttpc::Result<attohttpc::Response> {
     builder.send()
 }
 
+/// Sends a POST request to `url` with a request timeout if one was set.
+#[cfg(feature = "http-credentials")]
+pub(crate) fn http_post(
+    url: &str,
+    headers: &[(&'static str, String)],
+    body: String,
+) -> attohttpc::Result<attohttpc::Response> {
+    let mut builder = attohttpc::post(url);
+    for (name, value) in headers {
+        builder = builder.try_header(*name, value.as_str())?;
+    }
+
+    let timeout_ms = REQUEST_TIMEOUT_MS.load(Ordering::Relaxed);
+    if timeout_ms > 0 {
+        builder = builder.timeout(Duration::from_millis(timeout_ms as u64));
+    }
+
+    builder.text(body).send()
+}
+
 impl Credentials {
+    /// Whether the credentials expire within `buffer` from now. Credentials
+    /// without an `expiration` never do.
+    pub fn expires_within(&self, buffer: Duration) -> bool {
+        match self.expiration {
+            Some(expiration) => expiration.0 <= OffsetDateTime::now_utc() + buffer,
+            None => false,
+        }
+    }
+
     #[cfg(feature = "http-credentials")]
     pub fn refresh(&mut self) -> Result<(), CredentialsError> {
         if let Some(expiration) = self.expiration {
@@ -213,9 +258,25 @@ impl Credentials {
         role_arn: &str,
         session_name: &str,
         web_identity_token: &str,
+    ) -> Result<Credentials, CredentialsError> {
+        Credentials::from_sts_with_endpoint(
+            crate::sts::STS_ENDPOINT,
+            role_arn,
+            session_name,
+            web_identity_token,
+        )
+    }
+
+    /// Like [`Credentials::from_sts`], against the STS `endpoint`.
+    #[cfg(feature = "http-credentials")]
+    pub fn from_sts_with_endpoint(
+        endpoint: &str,
+        role_arn: &str,
+        session_name: &str,
+        web_identity_token: &str,
     ) -> Result<Credentials, CredentialsError> {
         let url = Url::parse_with_params(
-            "https://sts.amazonaws.com/",
+            endpoint,
             &[
                 ("Action", "AssumeRoleWithWebIdentity"),
                 ("RoleSessionName", session_name),
@@ -276,6 +337,11 @@ impl Credentials {
 
     /// Initialize Credentials directly with key ID, secret key, and optional
     /// token.
+    ///
+    /// Without an access key, looks up STS web identity, environment, profile
+    /// and instance metadata credentials in turn, like
+    /// [`ProviderChain::default_chain`](crate::ProviderChain::default_chain).
+    /// If all of them fail, the [`CredentialsError::Chain`] error keeps why.
     #[cfg(feature = "http-credentials")]
     pub fn new(
         access_key: Option<&str>,
@@ -294,11 +360,14 @@ impl Credentials {
             });
         }
 
-        Credentials::from_sts_env("aws-creds")
-            .or_else(|_| Credentials::from_env())
-            .or_else(|_| Credentials::from_profile(profile))
-            .or_else(|_| Credentials::from_instance_metadata())
-            .map_err(|_| CredentialsError::NoCredentials)
+        let mut errors = Vec::new();
+        for (name, lookup) in crate::chain::default_lookups(profile.map(String::from)) {
+            match lookup() {
+                Ok(credentials) => return Ok(credentials),
+                Err(error) => errors.push((name.to_string(), error)),
+            }
+        }
+        Err(CredentialsError::Chain(errors))
     }
 
     pub fn from_env_specific(
@@ -327,67 +396,22 @@ impl Credentials {
 
     #[cfg(feature = "http-credentials")]
     pub fn from_instance_metadata() -> Result<Credentials, CredentialsError> {
-        let resp: CredentialsFromInstanceMetadata =
-            match env::var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI") {
-                Ok(credentials_path) => {
-                    // We are on ECS
-                    attohttpc::get(format!("http://169.254.170.2{}", credentials_path))
-                        .send()?
-                        .json()?
-                }
-                Err(_) => {
-                    if !is_ec2() {
-                        return Err(CredentialsError::NotEc2);
-                    }
-
-                    let role = attohttpc::get(
-                        "http://169.254.169.254/latest/meta-data/iam/security-credentials",
-                    )
-                    .send()?
-                    .text()?;
-
-                    attohttpc::get(format!(
-                        "http://169.254.169.254/latest/meta-data/iam/security-credentials/{}",
-                        role
-                    ))
-                    .send()?
-                    .json()?
-                }
-            };
+        // We are on ECS or EKS
+        if let Some(container) = crate::container::ContainerCredentials::from_env()? {
+            return container.credentials();
+        }
 
-        Ok(Credentials {
-            access_key: Some(resp.access_key_id),
-            secret_key: Some(resp.secret_access_key),
-            security_token: Some(resp.token),
-            expiration: Some(resp.expiration),
-            session_token: None,
-        })
+        // An overridden endpoint may serve metadata anywhere.
+        if env::var_os("AWS_EC2_METADATA_SERVICE_ENDPOINT").is_none() && !is_ec2() {
+            return Err(CredentialsError::NotEc2);
+        }
+        crate::imds::shared()?.credentials()
     }
 
+    /// Load credentials from the `section` profile, `AWS_PROFILE` or
+    /// `default`, see [`Profile`](crate::Profile).
     pub fn from_profile(section: Option<&str>) -> Result<Credentials, CredentialsError> {
-        let home_dir = home::home_dir().ok_or(CredentialsError::HomeDir)?;
-        let profile = format!("{}/.aws/credentials", home_dir.display());
-        let conf = Ini::load_from_file(profile)?;
-        let section = section.unwrap_or("default");
-        let data = conf
-            .section(Some(section))
-            .ok_or(CredentialsError::ConfigNotFound)?;
-        let access_key = data
-            .get("aws_access_key_id")
-            .map(|s| s.to_string())
-            .ok_or(CredentialsError::ConfigMissingAccessKeyId)?;
-        let secret_key = data
-            .get("aws_secret_access_key")
-            .map(|s| s.to_string())
-            .ok_or(CredentialsError::ConfigMissingSecretKey)?;
-        let credentials = Credentials {
-            access_key: Some(access_key),
-            secret_key: Some(secret_key),
-            security_token: data.get("aws_security_token").map(|s| s.to_string()),
-            session_token: data.get("aws_session_token").map(|s| s.to_string()),
-            expiration: None,
-        };
-        Ok(credentials)
+        Profile::load(section)?.credentials()
     }
 }
 
@@ -414,11 +438,11 @@ fn is_ec2() -> bool {
 
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
-struct CredentialsFromInstanceMetadata {
-    access_key_id: String,
-    secret_access_key: String,
-    token: String,
-    expiration: Rfc3339OffsetDateTime, // TODO fix #163
+pub(crate) struct CredentialsFromInstanceMetadata {
+    pub(crate) access_key_id: String,
+    pub(crate) secret_access_key: String,
+    pub(crate) token: String,
+    pub(crate) expiration: Rfc3339OffsetDateTime, // TODO fix #163
 }
 
 #[cfg(test)]
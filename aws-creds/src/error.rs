@@ -25,8 +25,40 @@ pub enum CredentialsError {
     Io(#[from] std::io::Error),
     #[error("env var: {0}")]
     Env(#[from] std::env::VarError),
+    #[error("blocking credentials task: {0}")]
+    Join(#[from] tokio::task::JoinError),
+    #[error("profile: {0}")]
+    Profile(String),
+    #[error("serde_json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("Instance metadata is disabled")]
+    ImdsDisabled,
+    #[error("Invalid instance metadata endpoint mode {0}")]
+    ImdsEndpointMode(String),
+    #[error("Instance metadata responded with {0}: {1}")]
+    Imds(u16, String),
+    #[error("Container credentials are not allowed from {0}")]
+    ContainerUri(String),
+    #[error("Container credentials responded with {0}: {1}")]
+    Container(u16, String),
+    #[error("STS responded with {0}: {1}")]
+    Sts(u16, String),
+    #[error("No credentials from {}", chain_errors(.0))]
+    Chain(Vec<(String, CredentialsError)>),
     #[error("Invalid home dir")]
     HomeDir,
     #[error("Could not get valid credentials from STS, ENV, Profile or Instance metadata")]
     NoCredentials,
 }
+
+/// Lists the errors of a provider chain as `name: error`.
+fn chain_errors(errors: &[(String, CredentialsError)]) -> String {
+    if errors.is_empty() {
+        return "an empty provider chain".to_string();
+    }
+    errors
+        .iter()
+        .map(|(name, error)| format!("{}: {}", name, error))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
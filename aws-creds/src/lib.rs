@@ -1,9 +1,29 @@
 #![allow(unused_imports)]
 #![forbid(unsafe_code)]
 
+mod chain;
+pub use chain::{BlockingProvider, ProviderChain, ResolvedCredentials};
 mod credentials;
 pub use credentials::*;
+#[cfg(feature = "http-credentials")]
+mod container;
 pub mod error;
+#[cfg(test)]
+mod fake_server;
+#[cfg(feature = "http-credentials")]
+pub use container::ContainerCredentials;
+#[cfg(feature = "http-credentials")]
+mod imds;
+#[cfg(feature = "http-credentials")]
+pub use imds::InstanceMetadata;
+mod profile;
+pub use profile::Profile;
+mod provider;
+pub use provider::*;
+#[cfg(feature = "http-credentials")]
+mod sts;
+#[cfg(feature = "http-credentials")]
+pub use sts::*;
 
 // Reexport for e.g. users who need to build Credentials
 pub use time;
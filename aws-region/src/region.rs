@@ -87,16 +87,52 @@ pub enum Region {
     DoSgp1,
     /// Digital Ocean fra1
     DoFra1,
+    /// OVHcloud gra
+    OvhGra,
+    /// OVHcloud rbx
+    OvhRbx,
+    /// OVHcloud sbg
+    OvhSbg,
+    /// OVHcloud de
+    OvhDe,
+    /// OVHcloud uk
+    OvhUk,
+    /// OVHcloud waw
+    OvhWaw,
+    /// OVHcloud bhs
+    OvhBhs,
+    /// OVHcloud ca-east-tor
+    OvhCaEastTor,
+    /// OVHcloud sgp
+    OvhSgp,
     /// Yandex Object Storage
     Yandex,
     /// Wasabi us-east-1
     WaUsEast1,
     /// Wasabi us-east-2
     WaUsEast2,
+    /// Wasabi us-central-1
+    WaUsCentral1,
     /// Wasabi us-west-1
     WaUsWest1,
+    /// Wasabi ca-central-1
+    WaCaCentral1,
     /// Wasabi eu-central-1
     WaEuCentral1,
+    /// Wasabi eu-central-2
+    WaEuCentral2,
+    /// Wasabi eu-west-1
+    WaEuWest1,
+    /// Wasabi eu-west-2
+    WaEuWest2,
+    /// Wasabi ap-northeast-1
+    WaApNortheast1,
+    /// Wasabi ap-northeast-2
+    WaApNortheast2,
+    /// Wasabi ap-southeast-1
+    WaApSoutheast1,
+    /// Wasabi ap-southeast-2
+    WaApSoutheast2,
     /// Custom region
     R2 {
         account_id: String,
@@ -142,8 +178,26 @@ impl fmt::Display for Region {
             Yandex => write!(f, "ru-central1"),
             WaUsEast1 => write!(f, "us-east-1"),
             WaUsEast2 => write!(f, "us-east-2"),
+            WaUsCentral1 => write!(f, "us-central-1"),
             WaUsWest1 => write!(f, "us-west-1"),
+            WaCaCentral1 => write!(f, "ca-central-1"),
             WaEuCentral1 => write!(f, "eu-central-1"),
+            WaEuCentral2 => write!(f, "eu-central-2"),
+            WaEuWest1 => write!(f, "eu-west-1"),
+            WaEuWest2 => write!(f, "eu-west-2"),
+            WaApNortheast1 => write!(f, "ap-northeast-1"),
+            WaApNortheast2 => write!(f, "ap-northeast-2"),
+            WaApSoutheast1 => write!(f, "ap-southeast-1"),
+            WaApSoutheast2 => write!(f, "ap-southeast-2"),
+            OvhGra => write!(f, "gra"),
+            OvhRbx => write!(f, "rbx"),
+            OvhSbg => write!(f, "sbg"),
+            OvhDe => write!(f, "de"),
+            OvhUk => write!(f, "uk"),
+            OvhWaw => write!(f, "waw"),
+            OvhBhs => write!(f, "bhs"),
+            OvhCaEastTor => write!(f, "ca-east-tor"),
+            OvhSgp => write!(f, "sgp"),
             R2 { .. } => write!(f, "auto"),
             Custom { ref region, .. } => write!(f, "{}", region),
         }
@@ -188,8 +242,17 @@ impl FromStr for Region {
             "ru-central1" => Ok(Yandex),
             "wa-us-east-1" => Ok(WaUsEast1),
             "wa-us-east-2" => Ok(WaUsEast2),
+            "wa-us-central-1" => Ok(WaUsCentral1),
             "wa-us-west-1" => Ok(WaUsWest1),
+            "wa-ca-central-1" => Ok(WaCaCentral1),
             "wa-eu-central-1" => Ok(WaEuCentral1),
+            "wa-eu-central-2" => Ok(WaEuCentral2),
+            "wa-eu-west-1" => Ok(WaEuWest1),
+            "wa-eu-west-2" => Ok(WaEuWest2),
+            "wa-ap-northeast-1" => Ok(WaApNortheast1),
+            "wa-ap-northeast-2" => Ok(WaApNortheast2),
+            "wa-ap-southeast-1" => Ok(WaApSoutheast1),
+            "wa-ap-southeast-2" => Ok(WaApSoutheast2),
             x => Ok(Custom {
                 region: x.to_string(),
                 endpoint: x.to_string(),
@@ -235,8 +298,26 @@ impl Region {
             Yandex => String::from("storage.yandexcloud.net"),
             WaUsEast1 => String::from("s3.us-east-1.wasabisys.com"),
             WaUsEast2 => String::from("s3.us-east-2.wasabisys.com"),
+            WaUsCentral1 => String::from("s3.us-central-1.wasabisys.com"),
             WaUsWest1 => String::from("s3.us-west-1.wasabisys.com"),
+            WaCaCentral1 => String::from("s3.ca-central-1.wasabisys.com"),
             WaEuCentral1 => String::from("s3.eu-central-1.wasabisys.com"),
+            WaEuCentral2 => String::from("s3.eu-central-2.wasabisys.com"),
+            WaEuWest1 => String::from("s3.eu-west-1.wasabisys.com"),
+            WaEuWest2 => String::from("s3.eu-west-2.wasabisys.com"),
+            WaApNortheast1 => String::from("s3.ap-northeast-1.wasabisys.com"),
+            WaApNortheast2 => String::from("s3.ap-northeast-2.wasabisys.com"),
+            WaApSoutheast1 => String::from("s3.ap-southeast-1.wasabisys.com"),
+            WaApSoutheast2 => String::from("s3.ap-southeast-2.wasabisys.com"),
+            OvhGra => String::from("s3.gra.io.cloud.ovh.net"),
+            OvhRbx => String::from("s3.rbx.io.cloud.ovh.net"),
+            OvhSbg => String::from("s3.sbg.io.cloud.ovh.net"),
+            OvhDe => String::from("s3.de.io.cloud.ovh.net"),
+            OvhUk => String::from("s3.uk.io.cloud.ovh.net"),
+            OvhWaw => String::from("s3.waw.io.cloud.ovh.net"),
+            OvhBhs => String::from("s3.bhs.io.cloud.ovh.net"),
+            OvhCaEastTor => String::from("s3.ca-east-tor.io.cloud.ovh.net"),
+            OvhSgp => String::from("s3.sgp.io.cloud.ovh.net"),
             R2 { ref account_id } => format!("{}.r2.cloudflarestorage.com", account_id),
             Custom { ref endpoint, .. } => endpoint.to_string(),
         }
@@ -273,19 +354,71 @@ impl Region {
         }
     }
 
-    /// Attempts to create a Region from AWS_REGION and AWS_ENDPOINT environment variables
+    /// Attempts to create a Region from AWS_REGION and AWS_ENDPOINT environment variables,
+    /// falling back to the `region` and `endpoint_url` of the AWS profile with the
+    /// `profile` feature
     pub fn from_default_env() -> Result<Region, RegionError> {
-        if let Ok(endpoint) = env::var("AWS_ENDPOINT") {
-            Ok(Region::Custom {
-                region: env::var("AWS_REGION")?,
-                endpoint,
-            })
-        } else {
-            Ok(Region::from_str(&env::var("AWS_REGION")?)?)
+        let region = env::var("AWS_REGION").ok();
+        let endpoint = env::var("AWS_ENDPOINT").ok();
+        #[cfg(feature = "profile")]
+        let (region, endpoint) = with_profile(region, endpoint);
+
+        let region = region.ok_or(env::VarError::NotPresent)?;
+        match endpoint {
+            Some(endpoint) => Ok(Region::Custom { region, endpoint }),
+            None => Ok(Region::from_str(&region)?),
         }
     }
 }
 
+/// Fill in what the environment leaves unset from the AWS profile.
+#[cfg(feature = "profile")]
+fn with_profile(
+    region: Option<String>,
+    endpoint: Option<String>,
+) -> (Option<String>, Option<String>) {
+    if region.is_some() && endpoint.is_some() {
+        return (region, endpoint);
+    }
+    match awscreds::Profile::load(None) {
+        Ok(profile) => fill_from_profile(region, endpoint, &profile),
+        Err(_) => (region, endpoint),
+    }
+}
+
+#[cfg(feature = "profile")]
+fn fill_from_profile(
+    region: Option<String>,
+    endpoint: Option<String>,
+    profile: &awscreds::Profile,
+) -> (Option<String>, Option<String>) {
+    (
+        region.or_else(|| profile.region().map(String::from)),
+        endpoint.or_else(|| profile.endpoint_url().map(String::from)),
+    )
+}
+
+#[cfg(feature = "profile")]
+#[test]
+fn region_from_profile() {
+    let config = env::temp_dir().join(format!("awsregion-{}-config", std::process::id()));
+    std::fs::write(
+        &config,
+        "[profile minio]\nregion = eu-west-1\nendpoint_url = http://localhost:9000\n",
+    )
+    .unwrap();
+    let profile = awscreds::Profile::from_files("minio", None, Some(&config)).unwrap();
+    std::fs::remove_file(&config).unwrap();
+
+    let (region, endpoint) = fill_from_profile(None, None, &profile);
+    assert_eq!(region.as_deref(), Some("eu-west-1"));
+    assert_eq!(endpoint.as_deref(), Some("http://localhost:9000"));
+
+    let (region, endpoint) = fill_from_profile(Some("us-east-1".to_string()), None, &profile);
+    assert_eq!(region.as_deref(), Some("us-east-1"));
+    assert_eq!(endpoint.as_deref(), Some("http://localhost:9000"));
+}
+
 #[test]
 fn yandex_object_storage() {
     let yandex = Region::Custom {
@@ -305,3 +438,13 @@ fn test_region_eu_central_2() {
     let region = "eu-central-2".parse::<Region>().unwrap();
     assert_eq!(region.endpoint(), "s3.eu-central-2.amazonaws.com");
 }
+
+#[test]
+fn wasabi_and_ovh_regions() {
+    let region = "wa-eu-west-2".parse::<Region>().unwrap();
+    assert_eq!(region, Region::WaEuWest2);
+    assert_eq!(region.endpoint(), "s3.eu-west-2.wasabisys.com");
+
+    assert_eq!(Region::OvhGra.to_string(), "gra");
+    assert_eq!(Region::OvhGra.endpoint(), "s3.gra.io.cloud.ovh.net");
+}
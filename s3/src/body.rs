@@ -0,0 +1,529 @@
+//! Request bodies for object uploads.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use bytes::{Bytes, BytesMut};
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt, TryStreamExt};
+use http::header::{HeaderName, HeaderValue, CONTENT_ENCODING};
+use http::HeaderMap;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::Frame;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::io::ReaderStream;
+
+use crate::checksum::{Checksum, ChecksumAlgorithm};
+use crate::error::S3Error;
+use crate::signing::{self, ChunkSigner};
+use crate::transport::{self, Body};
+use crate::UNSIGNED_PAYLOAD;
+
+type ByteStream = BoxStream<'static, Result<Bytes, S3Error>>;
+
+/// Size of the chunks streamed content is signed in, see [`PutBody::with_signed_chunks`].
+pub const SIGNED_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The content of an object upload.
+///
+/// Content held in memory is signed as a whole, and retried like any other
+/// request. Content read from an [`AsyncRead`] or a [`Stream`] is sent as it
+/// is produced, without being buffered, with an `UNSIGNED-PAYLOAD` signature
+/// unless [signed chunks](Self::with_signed_chunks) are used. As it can only
+/// be read once, such a request is never retried.
+///
+/// # Example
+///
+/// ```no_run
+/// use s3::bucket::Bucket;
+/// use s3::creds::Credentials;
+/// use s3::PutBody;
+/// use anyhow::Result;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let bucket = Bucket::new("rust-s3-test", "us-east-1".parse()?, Credentials::default()?)?;
+///
+/// let file = tokio::fs::File::open("large_file").await?;
+/// let content_length = file.metadata().await?.len();
+/// let body = PutBody::from_reader(file, content_length);
+///
+/// bucket.put_object_body("/large_file", body).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PutBody {
+    content: Content,
+    content_length: u64,
+    signing: StreamSigning,
+}
+
+/// How streamed content is signed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StreamSigning {
+    Unsigned,
+    Chunked,
+    ChunkedWithTrailer(ChecksumAlgorithm),
+}
+
+#[derive(Clone)]
+enum Content {
+    Bytes(Bytes),
+    // Shared between clones of the body, taken by whichever sends it first.
+    Stream(Arc<Mutex<Option<ByteStream>>>),
+}
+
+impl PutBody {
+    /// Content read from `reader`, which has to yield exactly
+    /// `content_length` bytes. Anything beyond that is not read.
+    pub fn from_reader<R>(reader: R, content_length: u64) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        let stream = ReaderStream::new(reader.take(content_length)).map_err(S3Error::from);
+        Self::from_byte_stream(stream.boxed(), content_length)
+    }
+
+    /// Content produced by `stream`, which has to yield exactly
+    /// `content_length` bytes in total.
+    pub fn from_stream<S, E>(stream: S, content_length: u64) -> Self
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let stream = stream.map_err(|e| S3Error::Io(std::io::Error::other(e)));
+        Self::from_byte_stream(stream.boxed(), content_length)
+    }
+
+    fn from_byte_stream(stream: ByteStream, content_length: u64) -> Self {
+        PutBody {
+            content: Content::Stream(Arc::new(Mutex::new(Some(stream)))),
+            content_length,
+            signing: StreamSigning::Unsigned,
+        }
+    }
+
+    /// Sign streamed content chunk by chunk, in aws-chunked encoding
+    /// (`STREAMING-AWS4-HMAC-SHA256-PAYLOAD`), instead of not at all. This
+    /// protects its integrity without hashing it all up front, at the cost
+    /// of a little overhead per chunk of [`SIGNED_CHUNK_SIZE`] bytes.
+    ///
+    /// Content held in memory is always signed as a whole.
+    pub fn with_signed_chunks(mut self) -> Self {
+        if self.signing == StreamSigning::Unsigned {
+            self.signing = StreamSigning::Chunked;
+        }
+        self
+    }
+
+    /// Sign streamed content chunk by chunk, and send a checksum of it,
+    /// computed while streaming, as a signed trailer
+    /// (`STREAMING-AWS4-HMAC-SHA256-PAYLOAD-TRAILER`).
+    ///
+    /// Content held in memory is always signed as a whole.
+    pub fn with_trailing_checksum(mut self, algorithm: ChecksumAlgorithm) -> Self {
+        self.signing = StreamSigning::ChunkedWithTrailer(algorithm);
+        self
+    }
+
+    pub fn len(&self) -> u64 {
+        self.content_length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.content_length == 0
+    }
+
+    /// The content, if it is held in memory.
+    pub fn as_bytes(&self) -> Option<&Bytes> {
+        match &self.content {
+            Content::Bytes(bytes) => Some(bytes),
+            Content::Stream(_) => None,
+        }
+    }
+
+    /// Whether the content can be sent more than once.
+    pub fn is_replayable(&self) -> bool {
+        matches!(self.content, Content::Bytes(_))
+    }
+
+    /// Whether the content is sent in signed aws-chunked encoding.
+    pub(crate) fn is_aws_chunked(&self) -> bool {
+        !self.is_replayable() && self.signing != StreamSigning::Unsigned
+    }
+
+    fn trailing_checksum(&self) -> Option<ChecksumAlgorithm> {
+        match self.signing {
+            StreamSigning::ChunkedWithTrailer(algorithm) if self.is_aws_chunked() => {
+                Some(algorithm)
+            }
+            _ => None,
+        }
+    }
+
+    /// The `x-amz-content-sha256` header for this content.
+    pub(crate) fn content_sha256(&self) -> String {
+        match (&self.content, self.signing) {
+            (Content::Bytes(bytes), _) => hex::encode(Sha256::digest(bytes)),
+            (Content::Stream(_), StreamSigning::Unsigned) => UNSIGNED_PAYLOAD.into(),
+            (Content::Stream(_), StreamSigning::Chunked) => signing::STREAMING_PAYLOAD.into(),
+            (Content::Stream(_), StreamSigning::ChunkedWithTrailer(_)) => {
+                signing::STREAMING_PAYLOAD_TRAILER.into()
+            }
+        }
+    }
+
+    /// The number of bytes sent, including the aws-chunked framing.
+    pub(crate) fn encoded_len(&self) -> u64 {
+        if !self.is_aws_chunked() {
+            return self.content_length;
+        }
+        let trailer_len = self
+            .trailing_checksum()
+            .map(|algorithm| (algorithm.header_name().len() + 1 + algorithm.encoded_len()) as u64);
+        signing::aws_chunked_len(self.content_length, SIGNED_CHUNK_SIZE as u64, trailer_len)
+    }
+
+    /// Add the headers describing aws-chunked content, if it is sent that way.
+    pub(crate) fn add_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        if self.is_aws_chunked() {
+            headers.insert(CONTENT_ENCODING, HeaderValue::from_static("aws-chunked"));
+            headers.insert(
+                HeaderName::from_static("x-amz-decoded-content-length"),
+                self.content_length.into(),
+            );
+        }
+        if let Some(algorithm) = self.trailing_checksum() {
+            headers.insert(
+                HeaderName::from_static("x-amz-trailer"),
+                HeaderValue::from_static(algorithm.header_name()),
+            );
+        }
+        Ok(())
+    }
+
+    /// The body to send, failing if streamed content has already been sent.
+    ///
+    /// `signer` is required for content sent in aws-chunked encoding.
+    pub(crate) fn to_body(&self, signer: Option<ChunkSigner>) -> Result<Body, S3Error> {
+        match &self.content {
+            Content::Bytes(bytes) => Ok(transport::full(bytes.clone())),
+            Content::Stream(stream) => {
+                let mut stream = stream.lock().unwrap().take().ok_or(S3Error::BodyConsumed)?;
+                if self.is_aws_chunked() {
+                    let signer = signer.ok_or(S3Error::ChunkSigningWithoutCredentials)?;
+                    stream = aws_chunked(stream, signer, self.trailing_checksum());
+                }
+                Ok(StreamBody::new(stream.map_ok(Frame::data)).boxed_unsync())
+            }
+        }
+    }
+}
+
+struct ChunkedState {
+    stream: ByteStream,
+    buffer: BytesMut,
+    signer: ChunkSigner,
+    checksum: Option<(ChecksumAlgorithm, Checksum)>,
+    done: bool,
+}
+
+/// Re-frame `stream` into signed chunks of [`SIGNED_CHUNK_SIZE`] bytes,
+/// followed by an empty final chunk and the optional checksum trailer.
+fn aws_chunked(
+    stream: ByteStream,
+    signer: ChunkSigner,
+    trailer: Option<ChecksumAlgorithm>,
+) -> ByteStream {
+    let state = ChunkedState {
+        stream,
+        buffer: BytesMut::new(),
+        signer,
+        checksum: trailer.map(|algorithm| (algorithm, algorithm.hasher())),
+        done: false,
+    };
+
+    futures::stream::try_unfold(state, |mut state| async move {
+        if state.done {
+            return Ok(None);
+        }
+        while state.buffer.len() < SIGNED_CHUNK_SIZE {
+            match state.stream.try_next().await? {
+                Some(bytes) => state.buffer.extend_from_slice(&bytes),
+                None => break,
+            }
+        }
+
+        let len = state.buffer.len().min(SIGNED_CHUNK_SIZE);
+        let chunk = state.buffer.split_to(len);
+        let signature = state.signer.sign_chunk(&chunk)?;
+        let mut framed = BytesMut::from(signing::chunk_header(chunk.len(), &signature).as_bytes());
+        framed.extend_from_slice(&chunk);
+
+        if let Some((_, checksum)) = &mut state.checksum {
+            checksum.update(&chunk);
+        }
+        if chunk.is_empty() {
+            state.done = true;
+            if let Some((algorithm, checksum)) = state.checksum.take() {
+                let trailer = format!("{}:{}", algorithm.header_name(), checksum.finalize());
+                let signature = state.signer.sign_trailer(&format!("{}\n", trailer))?;
+                framed.extend_from_slice(
+                    format!("{}\r\nx-amz-trailer-signature:{}\r\n", trailer, signature).as_bytes(),
+                );
+            }
+        }
+        framed.extend_from_slice(b"\r\n");
+
+        Ok(Some((framed.freeze(), state)))
+    })
+    .boxed()
+}
+
+impl fmt::Debug for PutBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PutBody")
+            .field("content_length", &self.content_length)
+            .field("replayable", &self.is_replayable())
+            .field("signing", &self.signing)
+            .finish()
+    }
+}
+
+impl From<Bytes> for PutBody {
+    fn from(bytes: Bytes) -> Self {
+        PutBody {
+            content_length: bytes.len() as u64,
+            content: Content::Bytes(bytes),
+            signing: StreamSigning::Unsigned,
+        }
+    }
+}
+
+impl From<Vec<u8>> for PutBody {
+    fn from(content: Vec<u8>) -> Self {
+        Bytes::from(content).into()
+    }
+}
+
+impl From<&[u8]> for PutBody {
+    fn from(content: &[u8]) -> Self {
+        Bytes::copy_from_slice(content).into()
+    }
+}
+
+impl From<String> for PutBody {
+    fn from(content: String) -> Self {
+        Bytes::from(content).into()
+    }
+}
+
+impl From<&'static str> for PutBody {
+    fn from(content: &'static str) -> Self {
+        Bytes::from_static(content.as_bytes()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use bytes::Bytes;
+    use http::header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH};
+    use time::macros::datetime;
+
+    use super::PutBody;
+    use crate::checksum::ChecksumAlgorithm;
+    use crate::command::Command;
+    use crate::error::S3Error;
+    use crate::request::{Request, RequestImpl};
+    use crate::signing::{self, ChunkSigner, STREAMING_PAYLOAD, STREAMING_PAYLOAD_TRAILER};
+    use crate::transport::{memory_bucket, InMemoryTransport, RecordedRequest};
+    use crate::Bucket;
+
+    #[tokio::test]
+    async fn reader_is_streamed_unsigned() {
+        let transport = InMemoryTransport::new();
+        let bucket = memory_bucket(&transport);
+        let content = b"hello world, and then some".as_slice();
+
+        let body = PutBody::from_reader(content, 11);
+        bucket.put_object_body("/test.txt", body).await.unwrap();
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.body, Bytes::from_static(b"hello world"));
+        assert_eq!(request.headers[CONTENT_LENGTH], "11");
+        assert_eq!(request.headers["x-amz-content-sha256"], "UNSIGNED-PAYLOAD");
+        assert!(request.headers.get("content-md5").is_none());
+    }
+
+    #[tokio::test]
+    async fn stream_is_streamed() {
+        let transport = InMemoryTransport::new();
+        let bucket = memory_bucket(&transport);
+        let chunks = ["hello", " ", "world"].map(|chunk| Ok::<_, io::Error>(Bytes::from(chunk)));
+
+        let body = PutBody::from_stream(futures::stream::iter(chunks), 11);
+        bucket.put_object_body("/test.txt", body).await.unwrap();
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.body, Bytes::from_static(b"hello world"));
+        assert_eq!(request.headers[CONTENT_LENGTH], "11");
+    }
+
+    #[tokio::test]
+    async fn bytes_are_signed() {
+        let transport = InMemoryTransport::new();
+        let bucket = memory_bucket(&transport);
+
+        bucket
+            .put_object_body("/test.txt", Bytes::from_static(b"hello"))
+            .await
+            .unwrap();
+
+        let request = &transport.requests()[0];
+        assert_eq!(
+            request.headers["x-amz-content-sha256"],
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert!(request.headers.get("content-md5").is_some());
+    }
+
+    #[tokio::test]
+    async fn streams_are_not_retried() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(
+            http::Response::builder()
+                .status(503)
+                .body(Bytes::new())
+                .unwrap(),
+        );
+        let bucket = memory_bucket(&transport);
+
+        let body = PutBody::from_reader(b"hello".as_slice(), 5);
+        let result = bucket.put_object_body("/test.txt", body.clone()).await;
+        if cfg!(feature = "fail-on-err") {
+            assert!(result.is_err());
+        } else {
+            assert_eq!(result.unwrap().status_code(), 503);
+        }
+        assert_eq!(transport.requests().len(), 1);
+
+        let result = bucket.put_object_body("/test.txt", body).await;
+        assert!(matches!(result, Err(S3Error::BodyConsumed)));
+    }
+
+    /// Split an aws-chunked body into its chunks and trailer, checking the
+    /// chunk signatures against the seed signature of the request.
+    fn decode_aws_chunked(request: &RecordedRequest) -> (Vec<u8>, Option<String>) {
+        let authorization = request.headers[AUTHORIZATION].to_str().unwrap();
+        let (_, seed_signature) = authorization.rsplit_once("Signature=").unwrap();
+        let datetime = datetime!(2013-05-24 0:00 UTC);
+        let region = "us-east-1".parse().unwrap();
+        let signing_key = signing::signing_key(
+            &datetime,
+            "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY",
+            &region,
+            "s3",
+        )
+        .unwrap();
+        let mut signer = ChunkSigner::new(signing_key, &datetime, &region, seed_signature).unwrap();
+
+        let mut rest = &request.body[..];
+        let mut data = Vec::new();
+        loop {
+            let line_end = rest.windows(2).position(|w| w == b"\r\n").unwrap();
+            let line = std::str::from_utf8(&rest[..line_end]).unwrap();
+            let (len, signature) = line.split_once(";chunk-signature=").unwrap();
+            let len = usize::from_str_radix(len, 16).unwrap();
+            let chunk = &rest[line_end + 2..line_end + 2 + len];
+            assert_eq!(signer.sign_chunk(chunk).unwrap(), signature);
+            data.extend_from_slice(chunk);
+            rest = &rest[line_end + 2 + len..];
+            if len == 0 {
+                break;
+            }
+            assert_eq!(&rest[..2], b"\r\n");
+            rest = &rest[2..];
+        }
+
+        if rest == b"\r\n" {
+            return (data, None);
+        }
+        let trailer = std::str::from_utf8(rest).unwrap();
+        let (trailer, signature) = trailer
+            .strip_suffix("\r\n\r\n")
+            .unwrap()
+            .split_once("\r\nx-amz-trailer-signature:")
+            .unwrap();
+        assert_eq!(
+            signer.sign_trailer(&format!("{}\n", trailer)).unwrap(),
+            signature
+        );
+        (data, Some(trailer.to_string()))
+    }
+
+    async fn put_at_fixed_date(bucket: &Bucket, body: PutBody) {
+        let request = RequestImpl {
+            bucket,
+            path: "/chunkObject.txt",
+            command: Command::PutObject {
+                content: body,
+                content_type: "text/plain",
+                multipart: None,
+                conditions: None,
+                options: None,
+            },
+            datetime: datetime!(2013-05-24 0:00 UTC),
+        };
+        request.response_data(true).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn signed_chunks() {
+        let transport = InMemoryTransport::new();
+        let bucket = memory_bucket(&transport);
+        let content = vec![b'a'; 66560];
+
+        let body =
+            PutBody::from_reader(std::io::Cursor::new(content.clone()), 66560).with_signed_chunks();
+        put_at_fixed_date(&bucket, body).await;
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.headers["x-amz-content-sha256"], STREAMING_PAYLOAD);
+        assert_eq!(request.headers[CONTENT_ENCODING], "aws-chunked");
+        assert_eq!(request.headers["x-amz-decoded-content-length"], "66560");
+        assert_eq!(request.headers[CONTENT_LENGTH], "66824");
+        assert_eq!(request.body.len(), 66824);
+
+        let (data, trailer) = decode_aws_chunked(request);
+        assert_eq!(data, content);
+        assert_eq!(trailer, None);
+    }
+
+    #[tokio::test]
+    async fn signed_chunks_with_trailer() {
+        let transport = InMemoryTransport::new();
+        let bucket = memory_bucket(&transport);
+        let content = vec![b'a'; 66560];
+
+        let body = PutBody::from_reader(std::io::Cursor::new(content.clone()), 66560)
+            .with_trailing_checksum(ChecksumAlgorithm::Crc32c);
+        put_at_fixed_date(&bucket, body).await;
+
+        let request = &transport.requests()[0];
+        assert_eq!(
+            request.headers["x-amz-content-sha256"],
+            STREAMING_PAYLOAD_TRAILER
+        );
+        assert_eq!(request.headers["x-amz-trailer"], "x-amz-checksum-crc32c");
+        assert_eq!(request.headers[CONTENT_LENGTH], "66946");
+        assert_eq!(request.body.len(), 66946);
+
+        let (data, trailer) = decode_aws_chunked(request);
+        assert_eq!(data, content);
+        assert_eq!(trailer.unwrap(), "x-amz-checksum-crc32c:sOO8/Q==");
+    }
+}
@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use awscreds::{Credentials, CredentialsProvider};
+use awsregion::Region;
+use http::{HeaderMap, HeaderValue};
+use tokio::sync::{Mutex, RwLock};
+
+use super::client::{self, HttpClient};
+use super::{Bucket, Query, DEFAULT_REQUEST_TIMEOUT};
+use crate::error::{BucketNameError, S3Error};
+use crate::multipart::MultipartConfig;
+use crate::retry::RetryPolicy;
+use crate::transport::HttpTransport;
+
+/// Configure and instantiate a [`Bucket`].
+///
+/// Unless set otherwise, the bucket uses anonymous credentials, subdomain
+/// style addressing, ListObjectsV2, the default [`RetryPolicy`] and 60 second
+/// connect, read and request timeouts.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use s3::{Bucket, RetryPolicy};
+/// use s3::creds::Credentials;
+///
+/// let bucket = Bucket::builder("rust-s3-test", "us-east-1".parse().unwrap())
+///     .credentials(Credentials::default().unwrap())
+///     .path_style()
+///     .connect_timeout(Duration::from_secs(5))
+///     .request_timeout(Duration::from_secs(30))
+///     .retry_policy(RetryPolicy::default().with_max_attempts(5))
+///     .user_agent("my-app/1.0")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct BucketBuilder {
+    name: String,
+    region: Region,
+    credentials: Option<Arc<RwLock<Credentials>>>,
+    credentials_provider: Option<Arc<dyn CredentialsProvider>>,
+    path_style: bool,
+    listobjects_v2: bool,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    multipart_config: MultipartConfig,
+    user_agent: Option<String>,
+    extra_headers: HeaderMap,
+    extra_query: Query,
+    ca_certificates_pem: Option<Vec<u8>>,
+    http_client: Option<HttpClient>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    validate_name: bool,
+}
+
+impl BucketBuilder {
+    pub fn new(name: &str, region: Region) -> Self {
+        BucketBuilder {
+            name: name.into(),
+            region,
+            credentials: None,
+            credentials_provider: None,
+            path_style: false,
+            listobjects_v2: true,
+            connect_timeout: DEFAULT_REQUEST_TIMEOUT,
+            read_timeout: DEFAULT_REQUEST_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            retry_policy: RetryPolicy::default(),
+            multipart_config: MultipartConfig::default(),
+            user_agent: None,
+            extra_headers: HeaderMap::new(),
+            extra_query: HashMap::new(),
+            ca_certificates_pem: None,
+            http_client: None,
+            transport: None,
+            validate_name: true,
+        }
+    }
+
+    pub fn credentials(self, credentials: Credentials) -> Self {
+        self.shared_credentials(Arc::new(RwLock::new(credentials)))
+    }
+
+    /// Use credentials shared with other buckets, so a refresh is seen by all of them.
+    pub fn shared_credentials(mut self, credentials: Arc<RwLock<Credentials>>) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Ask `provider` for credentials before every request, see
+    /// [`Bucket::refresh_credentials`].
+    ///
+    /// Providers are asked every time, so wrap them in a
+    /// [`CredentialsCache`](awscreds::CredentialsCache) unless they cache
+    /// themselves. Share the cache between buckets to share its refreshes.
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use s3::Bucket;
+    /// use s3::creds::{CredentialsCache, DefaultCredentialsProvider};
+    ///
+    /// let cache = Arc::new(CredentialsCache::new(DefaultCredentialsProvider::default()));
+    /// let bucket = Bucket::builder("rust-s3-test", "us-east-1".parse().unwrap())
+    ///     .credentials_provider(cache)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn credentials_provider(mut self, provider: Arc<dyn CredentialsProvider>) -> Self {
+        self.credentials_provider = Some(provider);
+        self
+    }
+
+    /// Use path-style urls and headers instead of the default subdomain style.
+    pub fn path_style(mut self) -> Self {
+        self.path_style = true;
+        self
+    }
+
+    /// Use the older ListObjects API, see [`Bucket::set_listobjects_v1`].
+    pub fn listobjects_v1(mut self) -> Self {
+        self.listobjects_v2 = false;
+        self
+    }
+
+    /// Timeout for establishing a connection, `None` for no timeout.
+    ///
+    /// Ignored if a custom [`transport`](Self::transport) is used.
+    pub fn connect_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.connect_timeout = timeout.into();
+        self
+    }
+
+    /// Timeout for every single read from or write to a connection, `None`
+    /// for no timeout.
+    ///
+    /// Ignored if a custom [`transport`](Self::transport) is used.
+    pub fn read_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.read_timeout = timeout.into();
+        self
+    }
+
+    /// Timeout for sending a request and receiving the response headers,
+    /// `None` for no timeout. Applies to each attempt separately.
+    pub fn request_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.request_timeout = timeout.into();
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// How streamed uploads are split into parts, see [`MultipartConfig`].
+    pub fn multipart_config(mut self, multipart_config: MultipartConfig) -> Self {
+        self.multipart_config = multipart_config;
+        self
+    }
+
+    /// The `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Headers sent with every request, see [`Bucket::add_header`].
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.extra_headers = headers;
+        self
+    }
+
+    /// Query pairs added to every request, see [`Bucket::add_query`].
+    pub fn default_query(mut self, query: Query) -> Self {
+        self.extra_query = query;
+        self
+    }
+
+    /// Trust the CA certificates in `pem`, in addition to the webpki roots.
+    ///
+    /// Ignored if a custom [`transport`](Self::transport) is used.
+    pub fn ca_certificates_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.ca_certificates_pem = Some(pem.into());
+        self
+    }
+
+    /// Share an existing hyper client, and its connection pool, instead of
+    /// creating a new one.
+    pub fn http_client(mut self, http_client: HttpClient) -> Self {
+        self.http_client = Some(http_client);
+        self.transport = None;
+        self
+    }
+
+    /// Send requests through `transport` instead of a new hyper client.
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self.http_client = None;
+        self
+    }
+
+    /// Whether to check the bucket name against the S3 naming rules when
+    /// building, `true` by default. Some S3 compatible services allow names
+    /// that S3 does not.
+    pub fn validate_name(mut self, validate: bool) -> Self {
+        self.validate_name = validate;
+        self
+    }
+
+    pub fn build(self) -> Result<Bucket, S3Error> {
+        if self.validate_name {
+            validate_bucket_name(&self.name).map_err(|reason| S3Error::InvalidBucketName {
+                name: self.name.clone(),
+                reason,
+            })?;
+        }
+
+        let mut extra_headers = self.extra_headers;
+        if let Some(user_agent) = self.user_agent {
+            extra_headers.insert(http::header::USER_AGENT, HeaderValue::try_from(user_agent)?);
+        }
+
+        let (transport, tls_config): (Arc<dyn HttpTransport>, _) =
+            match (self.transport, self.http_client) {
+                (Some(transport), _) => (transport, None),
+                (None, Some(http_client)) => (Arc::new(http_client), None),
+                (None, None) => {
+                    let tls_config =
+                        Arc::new(client::tls_config(self.ca_certificates_pem.as_deref())?);
+                    let http_client = client::create_client(
+                        self.connect_timeout,
+                        self.read_timeout,
+                        tls_config.clone(),
+                    )?;
+                    (Arc::new(http_client), Some(tls_config))
+                }
+            };
+
+        let credentials = match self.credentials {
+            Some(credentials) => credentials,
+            None => Arc::new(RwLock::new(Credentials::anonymous()?)),
+        };
+
+        Ok(Bucket {
+            name: self.name,
+            region: self.region,
+            credentials,
+            credentials_provider: self.credentials_provider,
+            credentials_refresh_lock: Arc::new(Mutex::new(())),
+            extra_headers,
+            extra_query: self.extra_query,
+            request_timeout: self.request_timeout,
+            path_style: self.path_style,
+            listobjects_v2: self.listobjects_v2,
+            retry_policy: self.retry_policy,
+            multipart_config: self.multipart_config,
+            connect_timeout: self.connect_timeout,
+            read_timeout: self.read_timeout,
+            transport,
+            tls_config,
+        })
+    }
+}
+
+impl Bucket {
+    /// Start configuring a [`Bucket`], see [`BucketBuilder`].
+    pub fn builder(name: &str, region: Region) -> BucketBuilder {
+        BucketBuilder::new(name, region)
+    }
+}
+
+/// Check `name` against the
+/// [S3 bucket naming rules](https://docs.aws.amazon.com/AmazonS3/latest/userguide/bucketnamingrules.html).
+pub fn validate_bucket_name(name: &str) -> Result<(), BucketNameError> {
+    if !(3..=63).contains(&name.len()) {
+        return Err(BucketNameError::Length(name.len()));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '.' || *c == '-'))
+    {
+        return Err(BucketNameError::InvalidCharacter(c));
+    }
+    let is_alphanumeric = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+    if !is_alphanumeric(name.chars().next()) || !is_alphanumeric(name.chars().last()) {
+        return Err(BucketNameError::InvalidBoundary);
+    }
+    if name.contains("..") {
+        return Err(BucketNameError::AdjacentPeriods);
+    }
+    if name.parse::<Ipv4Addr>().is_ok() {
+        return Err(BucketNameError::IpAddress);
+    }
+    for prefix in ["xn--", "sthree-", "amzn-s3-demo-"] {
+        if name.starts_with(prefix) {
+            return Err(BucketNameError::ReservedPrefix(prefix));
+        }
+    }
+    for suffix in ["-s3alias", "--ol-s3", ".mrap", "--x-s3"] {
+        if name.ends_with(suffix) {
+            return Err(BucketNameError::ReservedSuffix(suffix));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::header::USER_AGENT;
+
+    use super::validate_bucket_name;
+    use crate::error::{BucketNameError, S3Error};
+    use crate::retry::RetryPolicy;
+    use crate::transport::InMemoryTransport;
+    use crate::Bucket;
+
+    #[test]
+    fn bucket_names() {
+        assert!(validate_bucket_name("rust-s3-test").is_ok());
+        assert!(validate_bucket_name("my.bucket.123").is_ok());
+        assert_eq!(validate_bucket_name("ab"), Err(BucketNameError::Length(2)));
+        assert_eq!(
+            validate_bucket_name("My-Bucket"),
+            Err(BucketNameError::InvalidCharacter('M'))
+        );
+        assert_eq!(
+            validate_bucket_name("my_bucket"),
+            Err(BucketNameError::InvalidCharacter('_'))
+        );
+        assert_eq!(
+            validate_bucket_name("-bucket"),
+            Err(BucketNameError::InvalidBoundary)
+        );
+        assert_eq!(
+            validate_bucket_name("my..bucket"),
+            Err(BucketNameError::AdjacentPeriods)
+        );
+        assert_eq!(
+            validate_bucket_name("192.168.5.4"),
+            Err(BucketNameError::IpAddress)
+        );
+        assert_eq!(
+            validate_bucket_name("xn--bucket"),
+            Err(BucketNameError::ReservedPrefix("xn--"))
+        );
+        assert_eq!(
+            validate_bucket_name("bucket-s3alias"),
+            Err(BucketNameError::ReservedSuffix("-s3alias"))
+        );
+    }
+
+    #[test]
+    fn build_rejects_invalid_name() {
+        let result = Bucket::builder("Invalid_Name", "us-east-1".parse().unwrap()).build();
+        assert!(matches!(
+            result,
+            Err(S3Error::InvalidBucketName {
+                reason: BucketNameError::InvalidCharacter('I'),
+                ..
+            })
+        ));
+
+        Bucket::builder("Invalid_Name", "us-east-1".parse().unwrap())
+            .validate_name(false)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn build_applies_settings() {
+        let policy = RetryPolicy::none();
+        let bucket = Bucket::builder("rust-s3-test", "eu-central-1".parse().unwrap())
+            .path_style()
+            .listobjects_v1()
+            .connect_timeout(Duration::from_secs(1))
+            .read_timeout(None)
+            .request_timeout(Duration::from_secs(10))
+            .retry_policy(policy.clone())
+            .user_agent("my-app/1.0")
+            .build()
+            .unwrap();
+
+        assert!(bucket.is_path_style());
+        assert_eq!(bucket.connect_timeout(), Some(Duration::from_secs(1)));
+        assert_eq!(bucket.read_timeout(), None);
+        assert_eq!(bucket.request_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(bucket.retry_policy(), &policy);
+        assert_eq!(bucket.extra_headers()[USER_AGENT], "my-app/1.0");
+        assert_eq!(bucket.access_key().unwrap(), None);
+    }
+
+    #[test]
+    fn with_request_timeout_sets_client_timeouts() {
+        let bucket = Bucket::builder("rust-s3-test", "eu-central-1".parse().unwrap())
+            .connect_timeout(Duration::from_secs(1))
+            .build()
+            .unwrap()
+            .with_request_timeout(Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(bucket.request_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(bucket.connect_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(bucket.read_timeout(), Some(Duration::from_secs(10)));
+
+        let bucket = Bucket::builder("rust-s3-test", "eu-central-1".parse().unwrap())
+            .connect_timeout(Duration::from_secs(1))
+            .transport(InMemoryTransport::new())
+            .build()
+            .unwrap()
+            .with_request_timeout(Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(bucket.request_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(bucket.connect_timeout(), Some(Duration::from_secs(1)));
+    }
+}
@@ -1,6 +1,12 @@
-use crate::bucket::{Bucket, Request};
-use crate::command::Command;
+use futures::{StreamExt, TryStreamExt};
+
+use crate::bucket::{
+    error_from_response_data, Bucket, CopyObjectResult, CopyPartResult, Part, Request,
+};
+use crate::command::{Command, Multipart};
 use crate::error::S3Error;
+use crate::multipart::{MAX_PARTS, MAX_PART_SIZE};
+use crate::object_ops::{CopyObjectOptions, CopySource};
 use crate::request::RequestImpl;
 
 impl Bucket {
@@ -36,19 +42,442 @@ impl Bucket {
             let from = from.strip_prefix('/').unwrap_or(from);
             format!("{bucket}/{path}", bucket = self.name(), path = from)
         };
-        self.copy_object(fq_from, to).await
+        self.copy_object(fq_from, to, &CopyObjectOptions::default())
+            .await
+    }
+
+    /// Copy an object from any bucket, region or version into this bucket.
+    ///
+    /// # Example:
+    ///
+    /// ```rust,no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use s3::object_ops::{CopyObjectOptions, CopySource, Directive};
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let source = CopySource::new("rust-s3-other", "/from.file").with_version_id("v1");
+    /// let options = CopyObjectOptions::default()
+    ///     .with_metadata_directive(Directive::Replace)
+    ///     .with_content_type("text/plain");
+    /// let result = bucket.copy_object_from(source, "/to.file", options).await?;
+    /// println!("{}", result.etag);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_object_from<T: AsRef<str>>(
+        &self,
+        source: CopySource,
+        to: T,
+        options: CopyObjectOptions,
+    ) -> Result<CopyObjectResult, S3Error> {
+        let from = source.header_value();
+        let command = Command::CopyObject {
+            from: &from,
+            options: &options,
+        };
+        let request = RequestImpl::new(self, to.as_ref(), command)?;
+        let response_data = request.response_data(false).await?;
+        if !(200..300).contains(&response_data.status_code()) {
+            return Err(error_from_response_data(response_data)?);
+        }
+
+        Ok(quick_xml::de::from_reader(response_data.as_slice())?)
+    }
+
+    /// Copy file from an S3 path, internally within the same bucket, as a
+    /// multipart upload of `UploadPartCopy` requests.
+    ///
+    /// A single copy is limited to 5 GiB, this copies objects of any size.
+    /// The range copied by each part is picked from the source size, starting
+    /// from the part size of the bucket's
+    /// [`MultipartConfig`](crate::multipart::MultipartConfig), and up to
+    /// `concurrency` parts are copied at once. Sources no larger than a
+    /// single part are copied with a regular copy. The content type, other
+    /// content headers and user metadata of the source are kept, like with a
+    /// regular copy. See [`Bucket::copy_object_multipart_with_options`] to
+    /// copy encrypted objects.
+    ///
+    /// # Example:
+    ///
+    /// ```rust,no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let code = bucket.copy_object_multipart("/from.file", "/to.file").await?;
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_object_multipart<F: AsRef<str>, T: AsRef<str>>(
+        &self,
+        from: F,
+        to: T,
+    ) -> Result<u16, S3Error> {
+        self.copy_object_multipart_with_options(from, to, &CopyObjectOptions::default())
+            .await
+    }
+
+    /// Copy file from an S3 path, internally within the same bucket, as a
+    /// multipart upload, with the settings of `options`.
+    ///
+    /// The encryption of `options` applies to the copy, and its source
+    /// encryption and preconditions to every part. The content type and
+    /// metadata are only replaced with [`Directive::Replace`](crate::object_ops::Directive::Replace),
+    /// otherwise the ones of the source are kept.
+    ///
+    /// # Example:
+    ///
+    /// ```rust,no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use s3::object_ops::{CopyObjectOptions, Encryption};
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let key = [0u8; 32];
+    /// let options = CopyObjectOptions::default()
+    ///     .with_source_encryption(Encryption::CustomerKey(key))
+    ///     .with_encryption(Encryption::CustomerKey(key));
+    /// let code = bucket
+    ///     .copy_object_multipart_with_options("/from.file", "/to.file", &options)
+    ///     .await?;
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_object_multipart_with_options<F: AsRef<str>, T: AsRef<str>>(
+        &self,
+        from: F,
+        to: T,
+        options: &CopyObjectOptions,
+    ) -> Result<u16, S3Error> {
+        let (head, _) = match options.source_encryption() {
+            Some(encryption) => {
+                self.head_object_with_encryption(from.as_ref(), encryption)
+                    .await?
+            }
+            None => self.head_object(from.as_ref()).await?,
+        };
+        let content_length = head.content_length.unwrap_or_default().max(0) as u64;
+        let source = CopySource::new(self.name(), from.as_ref()).header_value();
+
+        let part_size = (self.multipart_config.part_size as u64)
+            .max(content_length.div_ceil(MAX_PARTS as u64))
+            .min(MAX_PART_SIZE as u64);
+        if content_length <= part_size {
+            return self.copy_object(&source, to, options).await;
+        }
+        let part_count = content_length.div_ceil(part_size);
+        if part_count > MAX_PARTS as u64 {
+            return Err(S3Error::TooManyParts);
+        }
+
+        let msg = self
+            .initiate_multipart_upload_with_options(to.as_ref(), &options.multipart_options(&head))
+            .await?;
+        let path = msg.key;
+        let upload_id = &msg.upload_id;
+
+        let parts = futures::stream::iter(1..=part_count as u32)
+            .map(|part_number| {
+                let start = (part_number as u64 - 1) * part_size;
+                let end = (start + part_size).min(content_length) - 1;
+                self.upload_part_copy(&source, &path, part_number, upload_id, start, end, options)
+            })
+            .buffer_unordered(self.multipart_config.concurrency)
+            .try_collect::<Vec<Part>>()
+            .await;
+        let mut parts = match parts {
+            Ok(parts) => parts,
+            Err(error) => {
+                // if a part failed - abort the upload
+                self.abort_failed_upload(&path, upload_id).await;
+                return Err(error);
+            }
+        };
+        parts.sort_by_key(|part| part.part_number);
+
+        let response_data = self
+            .complete_multipart_upload(&path, upload_id, parts)
+            .await?;
+        Ok(response_data.status_code())
+    }
+
+    /// Copy the bytes `start..=end` of `from`, an `x-amz-copy-source` header
+    /// value, as part `part_number` of a multipart upload.
+    #[allow(clippy::too_many_arguments)]
+    async fn upload_part_copy(
+        &self,
+        from: &str,
+        path: &str,
+        part_number: u32,
+        upload_id: &str,
+        start: u64,
+        end: u64,
+        options: &CopyObjectOptions,
+    ) -> Result<Part, S3Error> {
+        let command = Command::UploadPartCopy {
+            from,
+            multipart: Multipart::new(part_number, upload_id),
+            start,
+            end,
+            options,
+        };
+        let request = RequestImpl::new(self, path, command)?;
+        let response_data = request.response_data(false).await?;
+        if !(200..300).contains(&response_data.status_code()) {
+            return Err(error_from_response_data(response_data)?);
+        }
+
+        let result: CopyPartResult = quick_xml::de::from_reader(response_data.as_slice())?;
+        Ok(Part::new(part_number, result.etag))
     }
 
     async fn copy_object<F: AsRef<str>, T: AsRef<str>>(
         &self,
         from: F,
         to: T,
+        options: &CopyObjectOptions,
     ) -> Result<u16, S3Error> {
         let command = Command::CopyObject {
             from: from.as_ref(),
+            options,
         };
         let request = RequestImpl::new(self, to.as_ref(), command)?;
         let response_data = request.response_data(false).await?;
         Ok(response_data.status_code())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http::Method;
+
+    use crate::multipart::{MultipartConfig, MIN_PART_SIZE};
+    use crate::object_ops::{CopyObjectOptions, CopySource, Directive, Encryption};
+    use crate::transport::{memory_bucket, InMemoryTransport};
+    use crate::Bucket;
+
+    #[tokio::test]
+    async fn copies_ranges() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(
+            http::Response::builder()
+                .header(http::header::CONTENT_LENGTH, 2 * MIN_PART_SIZE + 1)
+                .header(http::header::CONTENT_TYPE, "text/plain")
+                .header("x-amz-meta-origin", "upload")
+                .body(Bytes::new())
+                .unwrap(),
+        );
+        transport.push_response(http::Response::new(Bytes::from_static(
+            b"<InitiateMultipartUploadResult><Bucket>examplebucket</Bucket>\
+              <Key>to.file</Key><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+        )));
+        for part_number in 1..=3 {
+            transport.push_response(http::Response::new(Bytes::from(format!(
+                "<CopyPartResult><ETag>\"etag-{}\"</ETag>\
+                 <LastModified>2024-01-01T00:00:00.000Z</LastModified></CopyPartResult>",
+                part_number
+            ))));
+        }
+        let bucket = Bucket::builder("examplebucket", "us-east-1".parse().unwrap())
+            .multipart_config(
+                MultipartConfig::default()
+                    .with_part_size(MIN_PART_SIZE)
+                    .with_concurrency(1),
+            )
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let status = bucket
+            .copy_object_multipart("/my file+1.txt", "/to.file")
+            .await
+            .unwrap();
+        assert_eq!(status, 200);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 6);
+        assert_eq!(
+            requests[1].headers[http::header::CONTENT_TYPE],
+            "text/plain"
+        );
+        assert_eq!(requests[1].headers["x-amz-meta-origin"], "upload");
+        let ranges: Vec<_> = requests[2..5]
+            .iter()
+            .map(|request| {
+                assert_eq!(request.method, Method::PUT);
+                assert_eq!(
+                    request.headers["x-amz-copy-source"],
+                    "examplebucket/my%20file%2B1.txt"
+                );
+                request.headers["x-amz-copy-source-range"].to_str().unwrap()
+            })
+            .collect();
+        assert_eq!(
+            ranges,
+            [
+                format!("bytes=0-{}", MIN_PART_SIZE - 1),
+                format!("bytes={}-{}", MIN_PART_SIZE, 2 * MIN_PART_SIZE - 1),
+                format!("bytes={}-{}", 2 * MIN_PART_SIZE, 2 * MIN_PART_SIZE),
+            ]
+        );
+        assert_eq!(
+            requests[3].uri.query(),
+            Some("partNumber=2&uploadId=upload")
+        );
+        let completed = std::str::from_utf8(&requests[5].body).unwrap();
+        assert!(completed.contains("<PartNumber>3</PartNumber><ETag>\"etag-3\"</ETag>"));
+    }
+
+    #[cfg(feature = "fail-on-err")]
+    #[tokio::test]
+    async fn keeps_part_error_when_abort_fails() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(
+            http::Response::builder()
+                .header(http::header::CONTENT_LENGTH, MIN_PART_SIZE + 1)
+                .body(Bytes::new())
+                .unwrap(),
+        );
+        transport.push_response(http::Response::new(Bytes::from_static(
+            b"<InitiateMultipartUploadResult><Bucket>examplebucket</Bucket>\
+              <Key>to.file</Key><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+        )));
+        for (status, code) in [(400, "InvalidRequest"), (403, "AccessDenied")] {
+            transport.push_response(
+                http::Response::builder()
+                    .status(status)
+                    .body(Bytes::from(format!("<Error><Code>{}</Code></Error>", code)))
+                    .unwrap(),
+            );
+        }
+        let bucket = Bucket::builder("examplebucket", "us-east-1".parse().unwrap())
+            .multipart_config(
+                MultipartConfig::default()
+                    .with_part_size(MIN_PART_SIZE)
+                    .with_concurrency(1),
+            )
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let error = bucket
+            .copy_object_multipart("/from.file", "/to.file")
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Some("InvalidRequest"));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[3].method, Method::DELETE);
+    }
+
+    #[tokio::test]
+    async fn copies_ranges_with_customer_keys() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(
+            http::Response::builder()
+                .header(http::header::CONTENT_LENGTH, MIN_PART_SIZE + 1)
+                .body(Bytes::new())
+                .unwrap(),
+        );
+        transport.push_response(http::Response::new(Bytes::from_static(
+            b"<InitiateMultipartUploadResult><Bucket>examplebucket</Bucket>\
+              <Key>to.file</Key><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+        )));
+        for part_number in 1..=2 {
+            transport.push_response(http::Response::new(Bytes::from(format!(
+                "<CopyPartResult><ETag>\"etag-{}\"</ETag>\
+                 <LastModified>2024-01-01T00:00:00.000Z</LastModified></CopyPartResult>",
+                part_number
+            ))));
+        }
+        let bucket = Bucket::builder("examplebucket", "us-east-1".parse().unwrap())
+            .multipart_config(MultipartConfig::default().with_part_size(MIN_PART_SIZE))
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let options = CopyObjectOptions::default()
+            .with_source_encryption(Encryption::CustomerKey([1; 32]))
+            .with_encryption(Encryption::CustomerKey([2; 32]))
+            .with_if_match("\"source\"");
+        bucket
+            .copy_object_multipart_with_options("/from.file", "/to.file", &options)
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 5);
+        let source_key = &requests[0].headers["x-amz-server-side-encryption-customer-key"];
+        let destination_key = &requests[1].headers["x-amz-server-side-encryption-customer-key"];
+        assert_ne!(source_key, destination_key);
+        for request in &requests[2..4] {
+            assert_eq!(
+                &request.headers["x-amz-copy-source-server-side-encryption-customer-key"],
+                source_key
+            );
+            assert_eq!(
+                &request.headers["x-amz-server-side-encryption-customer-key"],
+                destination_key
+            );
+            assert_eq!(request.headers["x-amz-copy-source-if-match"], "\"source\"");
+        }
+    }
+
+    #[tokio::test]
+    async fn copies_from_other_bucket() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(Bytes::from_static(
+            b"<CopyObjectResult><ETag>\"etag\"</ETag>\
+              <LastModified>2024-01-01T00:00:00.000Z</LastModified></CopyObjectResult>",
+        )));
+        let bucket = memory_bucket(&transport);
+
+        let source = CopySource::new("other-bucket", "/from.file").with_version_id("v1");
+        let options = CopyObjectOptions::default().with_metadata_directive(Directive::Replace);
+        let result = bucket
+            .copy_object_from(source, "/to.file", options)
+            .await
+            .unwrap();
+        assert_eq!(result.etag, "\"etag\"");
+        assert_eq!(result.last_modified, "2024-01-01T00:00:00.000Z");
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::PUT);
+        assert_eq!(request.uri.path(), "/to.file");
+        assert_eq!(
+            request.headers["x-amz-copy-source"],
+            "other-bucket/from.file?versionId=v1"
+        );
+        assert_eq!(request.headers["x-amz-metadata-directive"], "REPLACE");
+    }
+}
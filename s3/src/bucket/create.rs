@@ -6,12 +6,6 @@ use crate::request::RequestImpl;
 use crate::BucketConfiguration;
 use awscreds::Credentials;
 use awsregion::Region;
-use http::HeaderMap;
-use std::{collections::HashMap, sync::Arc};
-use tokio::sync::RwLock;
-
-use super::client;
-use super::DEFAULT_REQUEST_TIMEOUT;
 
 #[allow(dead_code)]
 pub struct CreateBucketResponse {
@@ -122,17 +116,10 @@ impl Bucket {
     /// let bucket = Bucket::new(bucket_name, region, credentials).unwrap();
     /// ```
     pub fn new(name: &str, region: Region, credentials: Credentials) -> Result<Bucket, S3Error> {
-        Ok(Bucket {
-            name: name.into(),
-            region,
-            credentials: Arc::new(RwLock::new(credentials)),
-            extra_headers: HeaderMap::new(),
-            extra_query: HashMap::new(),
-            request_timeout: DEFAULT_REQUEST_TIMEOUT,
-            path_style: false,
-            listobjects_v2: true,
-            http_client: client::create_client(DEFAULT_REQUEST_TIMEOUT)?,
-        })
+        Bucket::builder(name, region)
+            .credentials(credentials)
+            .validate_name(false)
+            .build()
     }
 
     /// Instantiate a public existing `Bucket`.
@@ -147,16 +134,6 @@ impl Bucket {
     /// let bucket = Bucket::new_public(bucket_name, region).unwrap();
     /// ```
     pub fn new_public(name: &str, region: Region) -> Result<Bucket, S3Error> {
-        Ok(Bucket {
-            name: name.into(),
-            region,
-            credentials: Arc::new(RwLock::new(Credentials::anonymous()?)),
-            extra_headers: HeaderMap::new(),
-            extra_query: HashMap::new(),
-            request_timeout: DEFAULT_REQUEST_TIMEOUT,
-            path_style: false,
-            listobjects_v2: true,
-            http_client: client::create_client(DEFAULT_REQUEST_TIMEOUT)?,
-        })
+        Bucket::builder(name, region).validate_name(false).build()
     }
 }
@@ -1,6 +1,11 @@
+use crate::creds::{CredentialsCache, CredentialsProvider, DefaultCredentialsProvider};
 use crate::{bucket::Bucket, error::S3Error};
 
 impl Bucket {
+    /// Refresh expired credentials in place.
+    ///
+    /// This runs the credential lookup on the calling thread and fails if the
+    /// credentials are in use, prefer [`Bucket::refresh_credentials`].
     pub fn credentials_refresh(&self) -> Result<(), S3Error> {
         Ok(self
             .credentials
@@ -8,4 +13,97 @@ impl Bucket {
             .map_err(|_| S3Error::WLCredentials)?
             .refresh()?)
     }
+
+    /// Bring the credentials up to date, done before every request is signed.
+    ///
+    /// With a [`credentials_provider`](crate::BucketBuilder::credentials_provider)
+    /// the bucket uses whatever the provider returns. Otherwise credentials
+    /// expiring within [`CredentialsCache::DEFAULT_REFRESH_BUFFER`] are looked
+    /// up again by the [`DefaultCredentialsProvider`], and concurrent requests
+    /// wait for that single lookup.
+    ///
+    /// Presigning does not refresh, so call this first when presigning with
+    /// temporary credentials.
+    pub async fn refresh_credentials(&self) -> Result<(), S3Error> {
+        let credentials = match &self.credentials_provider {
+            Some(provider) => provider.provide_credentials().await?,
+            None => {
+                if !self.credentials_expiring().await {
+                    return Ok(());
+                }
+                let _refresh = self.credentials_refresh_lock.lock().await;
+                if !self.credentials_expiring().await {
+                    return Ok(());
+                }
+                DefaultCredentialsProvider::default()
+                    .provide_credentials()
+                    .await?
+            }
+        };
+
+        if *self.credentials.read().await != credentials {
+            *self.credentials.write().await = credentials;
+        }
+        Ok(())
+    }
+
+    async fn credentials_expiring(&self) -> bool {
+        self.credentials
+            .read()
+            .await
+            .expires_within(CredentialsCache::DEFAULT_REFRESH_BUFFER)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use http::header::AUTHORIZATION;
+
+    use crate::creds::{Credentials, CredentialsCache, CredentialsProvider, ProvideCredentials};
+    use crate::transport::InMemoryTransport;
+    use crate::Bucket;
+
+    #[derive(Debug, Default)]
+    struct Counting {
+        calls: AtomicUsize,
+    }
+
+    impl CredentialsProvider for Counting {
+        fn provide_credentials(&self) -> ProvideCredentials<'_> {
+            Box::pin(async move {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Credentials::new(Some("AKIDPROVIDED"), Some("secret"), None, None, None)
+            })
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn signs_with_provided_credentials() {
+        let transport = InMemoryTransport::new();
+        let provider = Arc::new(Counting::default());
+        let bucket = Bucket::builder("examplebucket", "us-east-1".parse().unwrap())
+            .credentials_provider(Arc::new(CredentialsCache::new(provider.clone())))
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let requests = (0..8).map(|i| {
+            let bucket = bucket.clone();
+            tokio::spawn(async move { bucket.get_object(format!("/{}.txt", i)).await })
+        });
+        for request in futures::future::join_all(requests).await {
+            request.unwrap().unwrap();
+        }
+
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 8);
+        for request in requests {
+            let authorization = request.headers[AUTHORIZATION].to_str().unwrap();
+            assert!(authorization.contains("Credential=AKIDPROVIDED/"));
+        }
+    }
 }
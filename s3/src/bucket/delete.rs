@@ -1,9 +1,14 @@
-use crate::bucket::Bucket;
+use crate::bucket::{
+    error_from_response_data, Bucket, DeleteObjectsData, DeleteObjectsResult, ObjectIdentifier,
+};
 use crate::command::Command;
 use crate::error::S3Error;
 use crate::request::RequestImpl;
 use crate::request::{Request, ResponseData};
 
+/// The most keys a single `DeleteObjects` request may contain.
+const DELETE_OBJECTS_BATCH_SIZE: usize = 1000;
+
 impl Bucket {
     /// Delete existing `Bucket`
     ///
@@ -60,6 +65,157 @@ impl Bucket {
         request.response_data(false).await
     }
 
+    /// Delete a specific version of a file from an S3 path, removing it
+    /// permanently instead of adding a delete marker.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let response_data = bucket.delete_object_version("/test.file", "3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY").await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_object_version<S: AsRef<str>>(
+        &self,
+        path: S,
+        version_id: &str,
+    ) -> Result<ResponseData, S3Error> {
+        let command = Command::DeleteObjectVersion { version_id };
+        let request = RequestImpl::new(self, path.as_ref(), command)?;
+        request.response_data(false).await
+    }
+
+    /// Delete many files at once, sending `DeleteObjects` requests of up to
+    /// 1000 keys each.
+    ///
+    /// Failing to delete single objects does not fail the call, check
+    /// [`DeleteObjectsResult::errors`]. In `quiet` mode only those failures
+    /// are reported.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::{Bucket, ObjectIdentifier};
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let objects = vec![
+    ///     ObjectIdentifier::new("/test.file"),
+    ///     ObjectIdentifier::new("/old.file").with_version_id("3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY"),
+    /// ];
+    /// let result = bucket.delete_objects(objects, false).await?;
+    /// for error in result.errors {
+    ///     println!("{}: {} {}", error.key, error.code, error.message);
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_objects<I>(
+        &self,
+        objects: I,
+        quiet: bool,
+    ) -> Result<DeleteObjectsResult, S3Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<ObjectIdentifier>,
+    {
+        let objects: Vec<ObjectIdentifier> = objects.into_iter().map(Into::into).collect();
+        let mut result = DeleteObjectsResult::default();
+
+        for batch in objects.chunks(DELETE_OBJECTS_BATCH_SIZE) {
+            let command = Command::DeleteObjects {
+                data: DeleteObjectsData {
+                    objects: batch.to_vec(),
+                    quiet,
+                },
+            };
+            let request = RequestImpl::new(self, "/", command)?;
+            let response_data = request.response_data(false).await?;
+            if !(200..300).contains(&response_data.status_code()) {
+                return Err(error_from_response_data(response_data)?);
+            }
+
+            let batch_result: DeleteObjectsResult =
+                quick_xml::de::from_reader(response_data.as_slice())?;
+            result.deleted.extend(batch_result.deleted);
+            result.errors.extend(batch_result.errors);
+        }
+
+        Ok(result)
+    }
+
+    /// Delete all files whose key starts with `prefix`, listing and deleting
+    /// one page of keys at a time. Like [`Bucket::delete_objects`] in quiet
+    /// mode, only the objects that could not be deleted are reported.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let result = bucket.delete_prefix("logs/2023/").await?;
+    /// assert!(result.errors.is_empty());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_prefix(&self, prefix: &str) -> Result<DeleteObjectsResult, S3Error> {
+        let mut result = DeleteObjectsResult::default();
+        let mut continuation_token = None;
+
+        loop {
+            let (list_bucket_result, _) = self
+                .list_page(prefix.to_string(), None, continuation_token, None, None)
+                .await?;
+            continuation_token = list_bucket_result.next_page_token();
+
+            let keys = list_bucket_result
+                .contents
+                .into_iter()
+                .map(|object| object.key);
+            let page_result = self.delete_objects(keys, true).await?;
+            result.errors.extend(page_result.errors);
+
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Delete tags from an S3 object.
     ///
     /// # Example:
@@ -91,3 +247,119 @@ impl Bucket {
         request.response_data(false).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use base64::engine::general_purpose;
+    use base64::Engine;
+    use bytes::Bytes;
+    use http::Method;
+
+    use crate::bucket::ObjectIdentifier;
+    use crate::transport::{memory_bucket, InMemoryTransport};
+
+    #[tokio::test]
+    async fn deletes_in_batches() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(Bytes::from_static(
+            b"<DeleteResult>\
+              <Deleted><Key>key-0</Key></Deleted>\
+              <Error><Key>key-1</Key><Code>AccessDenied</Code><Message>Access Denied</Message></Error>\
+              <Deleted><Key>key-2</Key><DeleteMarker>true</DeleteMarker>\
+              <DeleteMarkerVersionId>v2</DeleteMarkerVersionId></Deleted>\
+              </DeleteResult>",
+        )));
+        transport.push_response(http::Response::new(Bytes::from_static(
+            b"<DeleteResult><Deleted><Key>a&amp;b</Key><VersionId>v1</VersionId></Deleted></DeleteResult>",
+        )));
+        let bucket = memory_bucket(&transport);
+
+        let mut objects: Vec<ObjectIdentifier> = (0..1000)
+            .map(|i| ObjectIdentifier::new(format!("/key-{}", i)))
+            .collect();
+        objects.push(ObjectIdentifier::new("a&b").with_version_id("v1"));
+        let result = bucket.delete_objects(objects, false).await.unwrap();
+
+        let deleted: Vec<_> = result.deleted.iter().map(|d| d.key.as_str()).collect();
+        assert_eq!(deleted, ["key-0", "key-2", "a&b"]);
+        assert!(result.deleted[1].delete_marker);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].key, "key-1");
+        assert_eq!(result.errors[0].code, "AccessDenied");
+        assert_eq!(result.errors[0].message, "Access Denied");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        for request in &requests {
+            assert_eq!(request.method, Method::POST);
+            assert_eq!(request.uri.query(), Some("delete"));
+            let md5 = general_purpose::STANDARD.encode(md5::compute(&request.body).as_ref());
+            assert_eq!(request.headers["content-md5"], md5.as_str());
+        }
+        let first = std::str::from_utf8(&requests[0].body).unwrap();
+        assert_eq!(first.matches("<Object>").count(), 1000);
+        assert!(first.starts_with("<Delete><Object><Key>key-0</Key></Object>"));
+        assert_eq!(
+            requests[1].body,
+            "<Delete><Object><Key>a&amp;b</Key><VersionId>v1</VersionId></Object></Delete>"
+        );
+    }
+
+    #[tokio::test]
+    async fn deletes_prefix() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(Bytes::from_static(
+            b"<ListBucketResult><Name>examplebucket</Name><Prefix>logs/</Prefix>\
+              <IsTruncated>false</IsTruncated>\
+              <Contents><Key>logs/a</Key><LastModified>2024-01-01T00:00:00.000Z</LastModified>\
+              <Size>1</Size></Contents>\
+              <Contents><Key>logs/b</Key><LastModified>2024-01-01T00:00:00.000Z</LastModified>\
+              <Size>1</Size></Contents>\
+              </ListBucketResult>",
+        )));
+        transport.push_response(http::Response::new(Bytes::from_static(
+            b"<DeleteResult></DeleteResult>",
+        )));
+        let bucket = memory_bucket(&transport);
+
+        let result = bucket.delete_prefix("logs/").await.unwrap();
+        assert!(result.errors.is_empty());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].body,
+            "<Delete><Quiet>true</Quiet><Object><Key>logs/a</Key></Object>\
+             <Object><Key>logs/b</Key></Object></Delete>"
+        );
+    }
+
+    #[tokio::test]
+    async fn deletes_prefix_with_listobjects_v1() {
+        let transport = InMemoryTransport::new();
+        for (key, truncated) in [("logs/a", true), ("logs/b", false)] {
+            transport.push_response(http::Response::new(Bytes::from(format!(
+                "<ListBucketResult><Name>examplebucket</Name><Prefix>logs/</Prefix>\
+                 <IsTruncated>{}</IsTruncated>\
+                 <Contents><Key>{}</Key><LastModified>2024-01-01T00:00:00.000Z</LastModified>\
+                 <Size>1</Size></Contents>\
+                 </ListBucketResult>",
+                truncated, key
+            ))));
+            transport.push_response(http::Response::new(Bytes::from_static(
+                b"<DeleteResult></DeleteResult>",
+            )));
+        }
+        let bucket = memory_bucket(&transport).with_listobjects_v1();
+
+        bucket.delete_prefix("logs/").await.unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[2].method, Method::GET);
+        assert!(requests[2].uri.query().unwrap().contains("marker=logs%2Fa"));
+        assert!(std::str::from_utf8(&requests[3].body)
+            .unwrap()
+            .contains("<Key>logs/b</Key>"));
+    }
+}
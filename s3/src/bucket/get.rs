@@ -1,6 +1,8 @@
-use crate::bucket::{Bucket, Request};
+use crate::bucket::{Bucket, Request, VersioningConfiguration};
+use crate::checksum::ChecksumAlgorithm;
 use crate::command::Command;
 use crate::error::S3Error;
+use crate::object_ops::{Conditional, Conditions, Encryption};
 use crate::request::RequestImpl;
 use crate::request::ResponseData;
 
@@ -34,6 +36,155 @@ impl Bucket {
         request.response_data(false).await
     }
 
+    /// Gets file from an S3 path if `conditions` hold, e.g. to revalidate a
+    /// cached copy by its ETag.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use s3::object_ops::{Conditional, Conditions};
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let conditions = Conditions::default().with_if_none_match("\"9b2cf535f27731c974343645a3985328\"");
+    /// match bucket.get_object_with_conditions("/test.file", &conditions).await? {
+    ///     Conditional::Met(response_data) => println!("{} bytes", response_data.as_slice().len()),
+    ///     Conditional::NotModified => println!("cached copy is fresh"),
+    ///     Conditional::PreconditionFailed => println!("precondition failed"),
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_object_with_conditions<S: AsRef<str>>(
+        &self,
+        path: S,
+        conditions: &Conditions,
+    ) -> Result<Conditional<ResponseData>, S3Error> {
+        let command = Command::GetObjectWithOptions {
+            conditions: Some(conditions),
+            encryption: None,
+            checksum_mode: false,
+        };
+        let request = RequestImpl::new(self, path.as_ref(), command)?;
+        match request.response_data(false).await {
+            Ok(response_data) => Ok(Conditional::from_status(
+                response_data.status_code(),
+                response_data,
+            )),
+            Err(error) => Conditional::from_error(error),
+        }
+    }
+
+    /// Gets a file encrypted with SSE-C from an S3 path, other kinds of
+    /// encryption need no key to read.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use s3::object_ops::Encryption;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    /// let encryption = Encryption::CustomerKey([7; 32]);
+    ///
+    /// let response_data = bucket.get_object_with_encryption("/test.file", &encryption).await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_object_with_encryption<S: AsRef<str>>(
+        &self,
+        path: S,
+        encryption: &Encryption,
+    ) -> Result<ResponseData, S3Error> {
+        let command = Command::GetObjectWithOptions {
+            conditions: None,
+            encryption: Some(encryption),
+            checksum_mode: false,
+        };
+        let request = RequestImpl::new(self, path.as_ref(), command)?;
+        request.response_data(false).await
+    }
+
+    /// Gets a specific version of a file from an S3 path.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let response_data = bucket.get_object_version("/test.file", "3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY").await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_object_version<S: AsRef<str>>(
+        &self,
+        path: S,
+        version_id: &str,
+    ) -> Result<ResponseData, S3Error> {
+        let command = Command::GetObjectVersion { version_id };
+        let request = RequestImpl::new(self, path.as_ref(), command)?;
+        request.response_data(false).await
+    }
+
+    /// Gets the versioning state of the bucket.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let versioning = bucket.get_bucket_versioning().await?;
+    /// println!("{:?}", versioning.status);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_bucket_versioning(&self) -> Result<VersioningConfiguration, S3Error> {
+        let request = RequestImpl::new(self, "/", Command::GetBucketVersioning)?;
+        let response_data = request.response_data(false).await?;
+        Ok(quick_xml::de::from_reader(response_data.as_slice())?)
+    }
+
     /// Gets torrent from an S3 path.
     ///
     /// # Example:
@@ -184,6 +335,55 @@ impl Bucket {
         request.response_data_to_writer(writer).await
     }
 
+    /// Stream file from S3 path to a local file, generic over T: Write, and
+    /// verify it against the `algorithm` checksum stored with the object.
+    ///
+    /// The content is checked while it is written, a mismatch is reported
+    /// as [`S3Error::ChecksumMismatch`] once everything was written. Objects
+    /// stored without a checksum of `algorithm`, or uploaded in parts, are
+    /// not verified.
+    ///
+    /// # Example:
+    ///
+    /// ```rust,no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use s3::ChecksumAlgorithm;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    /// let mut output_file = tokio::fs::File::create("output_file").await?;
+    ///
+    /// let status_code = bucket
+    ///     .get_object_to_writer_with_checksum("/test.file", &mut output_file, ChecksumAlgorithm::Crc32c)
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_object_to_writer_with_checksum<T: AsyncWrite + Send + Unpin, S: AsRef<str>>(
+        &self,
+        path: S,
+        writer: &mut T,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<u16, S3Error> {
+        let command = Command::GetObjectWithOptions {
+            conditions: None,
+            encryption: None,
+            checksum_mode: true,
+        };
+        let request = RequestImpl::new(self, path.as_ref(), command)?;
+        request
+            .response_data_to_writer_with_checksum(writer, algorithm)
+            .await
+    }
+
     /// Stream file from S3 path to a local file using an async stream.
     ///
     /// # Example
@@ -312,3 +512,200 @@ impl Bucket {
         Ok((tags, result.status_code()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http::Method;
+
+    use crate::bucket::VersioningStatus;
+    use crate::checksum::ChecksumAlgorithm;
+    use crate::error::S3Error;
+    use crate::object_ops::{Conditional, Conditions, Encryption};
+    use crate::transport::{memory_bucket, InMemoryTransport};
+
+    #[tokio::test]
+    async fn versioned_requests() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(Bytes::from_static(b"old")));
+        transport.push_response(http::Response::new(Bytes::new()));
+        transport.push_response(http::Response::new(Bytes::new()));
+        transport.push_response(http::Response::new(Bytes::from_static(
+            b"<VersioningConfiguration><Status>Suspended</Status></VersioningConfiguration>",
+        )));
+        let bucket = memory_bucket(&transport);
+
+        let response_data = bucket
+            .get_object_version("/test.file", "3/L4kqtJl40")
+            .await
+            .unwrap();
+        assert_eq!(response_data.as_slice(), b"old");
+        bucket
+            .head_object_version("/test.file", "3/L4kqtJl40")
+            .await
+            .unwrap();
+        bucket
+            .delete_object_version("/test.file", "3/L4kqtJl40")
+            .await
+            .unwrap();
+        let versioning = bucket.get_bucket_versioning().await.unwrap();
+        assert_eq!(versioning.status, Some(VersioningStatus::Suspended));
+
+        let requests = transport.requests();
+        let methods: Vec<_> = requests.iter().map(|request| &request.method).collect();
+        assert_eq!(
+            methods,
+            [&Method::GET, &Method::HEAD, &Method::DELETE, &Method::GET]
+        );
+        for request in &requests[..3] {
+            assert_eq!(request.uri.path(), "/test.file");
+            assert_eq!(request.uri.query(), Some("versionId=3%2FL4kqtJl40"));
+        }
+        assert_eq!(requests[3].uri.query(), Some("versioning"));
+    }
+
+    #[tokio::test]
+    async fn conditional_requests() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(
+            http::Response::builder()
+                .status(304)
+                .body(Bytes::new())
+                .unwrap(),
+        );
+        transport.push_response(
+            http::Response::builder()
+                .status(412)
+                .body(Bytes::new())
+                .unwrap(),
+        );
+        transport.push_response(
+            http::Response::builder()
+                .status(412)
+                .body(Bytes::from_static(
+                    b"<Error><Code>PreconditionFailed</Code><Message>At least one of the pre-conditions you specified did not hold</Message><RequestId>1</RequestId></Error>",
+                ))
+                .unwrap(),
+        );
+        transport.push_response(http::Response::new(Bytes::from_static(b"new")));
+        let bucket = memory_bucket(&transport);
+
+        let revalidate = Conditions::default().with_if_none_match("\"etag\"");
+        let outcome = bucket
+            .get_object_with_conditions("/test.file", &revalidate)
+            .await
+            .unwrap();
+        assert!(matches!(outcome, Conditional::NotModified));
+        let outcome = bucket
+            .head_object_with_conditions(
+                "/test.file",
+                &Conditions::default().with_if_match("\"etag\""),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(outcome, Conditional::PreconditionFailed));
+        let outcome = bucket
+            .put_object_with_conditions(
+                "/test.file",
+                b"new".as_slice(),
+                "text/plain",
+                &Conditions::create_only(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(outcome, Conditional::PreconditionFailed));
+        let outcome = bucket
+            .get_object_with_conditions("/test.file", &revalidate)
+            .await
+            .unwrap();
+        assert_eq!(outcome.met().unwrap().as_slice(), b"new");
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].headers["if-none-match"], "\"etag\"");
+        assert_eq!(requests[1].method, Method::HEAD);
+        assert_eq!(requests[1].headers["if-match"], "\"etag\"");
+        assert_eq!(requests[2].method, Method::PUT);
+        assert_eq!(requests[2].headers["if-none-match"], "*");
+    }
+
+    #[tokio::test]
+    async fn customer_key_requests() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(Bytes::from_static(b"secret")));
+        transport.push_response(http::Response::new(Bytes::new()));
+        let bucket = memory_bucket(&transport);
+        let encryption = Encryption::CustomerKey([7; 32]);
+
+        let response_data = bucket
+            .get_object_with_encryption("/test.file", &encryption)
+            .await
+            .unwrap();
+        assert_eq!(response_data.as_slice(), b"secret");
+        bucket
+            .head_object_with_encryption("/test.file", &encryption)
+            .await
+            .unwrap();
+
+        for request in transport.requests() {
+            assert_eq!(
+                request.headers["x-amz-server-side-encryption-customer-key-md5"],
+                "y4HAEFCYWuvAXWFTtA1Qpg=="
+            );
+            assert!(!request.headers.contains_key("x-amz-server-side-encryption"));
+        }
+    }
+
+    #[tokio::test]
+    async fn verifies_checksums() {
+        let transport = InMemoryTransport::new();
+        for checksum in ["crUfeA==", "AAAAAA==", "AAAAAA==-2"] {
+            transport.push_response(
+                http::Response::builder()
+                    .header("x-amz-checksum-crc32c", checksum)
+                    .body(Bytes::from_static(b"Hello world"))
+                    .unwrap(),
+            );
+        }
+        let bucket = memory_bucket(&transport);
+
+        let mut writer = Vec::new();
+        bucket
+            .get_object_to_writer_with_checksum(
+                "/test.file",
+                &mut writer,
+                ChecksumAlgorithm::Crc32c,
+            )
+            .await
+            .unwrap();
+        assert_eq!(writer, b"Hello world");
+
+        let error = bucket
+            .get_object_to_writer_with_checksum(
+                "/test.file",
+                &mut Vec::new(),
+                ChecksumAlgorithm::Crc32c,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            S3Error::ChecksumMismatch { ref expected, ref computed, .. }
+                if expected == "AAAAAA==" && computed == "crUfeA=="
+        ));
+
+        // Checksums of multipart uploads are not verified
+        bucket
+            .get_object_to_writer_with_checksum(
+                "/test.file",
+                &mut Vec::new(),
+                ChecksumAlgorithm::Crc32c,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport.requests()[0].headers["x-amz-checksum-mode"],
+            "ENABLED"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::bucket::*;
 use crate::command::Command;
+use crate::object_ops::{Conditional, Conditions, Encryption};
 use crate::request::RequestImpl;
 
 impl Bucket {
@@ -35,4 +36,124 @@ impl Bucket {
         let header_object = HeadObjectResult::from(&headers);
         Ok((header_object, status))
     }
+
+    /// Head object from S3 if `conditions` hold.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use s3::object_ops::{Conditional, Conditions};
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let conditions = Conditions::default().with_if_match("\"9b2cf535f27731c974343645a3985328\"");
+    /// if let Conditional::Met((head_object_result, code)) =
+    ///     bucket.head_object_with_conditions("/test.png", &conditions).await?
+    /// {
+    ///     println!("{:?}", head_object_result.content_length);
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn head_object_with_conditions<S: AsRef<str>>(
+        &self,
+        path: S,
+        conditions: &Conditions,
+    ) -> Result<Conditional<(HeadObjectResult, u16)>, S3Error> {
+        let command = Command::HeadObjectWithOptions {
+            conditions: Some(conditions),
+            encryption: None,
+        };
+        let request = RequestImpl::new(self, path.as_ref(), command)?;
+        match request.response_header().await {
+            Ok((headers, status)) => Ok(Conditional::from_status(
+                status,
+                (HeadObjectResult::from(&headers), status),
+            )),
+            Err(error) => Conditional::from_error(error),
+        }
+    }
+
+    /// Head an object encrypted with SSE-C from S3.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use s3::object_ops::Encryption;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    /// let encryption = Encryption::CustomerKey([7; 32]);
+    ///
+    /// let (head_object_result, code) = bucket.head_object_with_encryption("/test.png", &encryption).await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn head_object_with_encryption<S: AsRef<str>>(
+        &self,
+        path: S,
+        encryption: &Encryption,
+    ) -> Result<(HeadObjectResult, u16), S3Error> {
+        let command = Command::HeadObjectWithOptions {
+            conditions: None,
+            encryption: Some(encryption),
+        };
+        let request = RequestImpl::new(self, path.as_ref(), command)?;
+        let (headers, status) = request.response_header().await?;
+        let header_object = HeadObjectResult::from(&headers);
+        Ok((header_object, status))
+    }
+
+    /// Head a specific version of an object from S3.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let (head_object_result, code) = bucket.head_object_version("/test.png", "3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY").await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn head_object_version<S: AsRef<str>>(
+        &self,
+        path: S,
+        version_id: &str,
+    ) -> Result<(HeadObjectResult, u16), S3Error> {
+        let command = Command::HeadObjectVersion { version_id };
+        let request = RequestImpl::new(self, path.as_ref(), command)?;
+        let (headers, status) = request.response_header().await?;
+        let header_object = HeadObjectResult::from(&headers);
+        Ok((header_object, status))
+    }
 }
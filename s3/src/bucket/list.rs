@@ -3,9 +3,13 @@ use crate::command::Command;
 use crate::error::S3Error;
 use crate::request::Request;
 use crate::request::RequestImpl;
-use crate::serde_types::{ListBucketResult, ListMultipartUploadsResult};
+use crate::serde_types::{
+    ListBucketResult, ListMultipartUploadsResult, ListPartsResult, ListVersionsResult,
+    MultipartUpload, Object,
+};
 use awscreds::Credentials;
 use awsregion::Region;
+use futures::{Stream, TryStreamExt};
 use serde::Deserialize;
 
 impl Bucket {
@@ -150,7 +154,7 @@ impl Bucket {
                     None,
                 )
                 .await?;
-            continuation_token = list_bucket_result.next_continuation_token.clone();
+            continuation_token = list_bucket_result.next_page_token();
             results.push(list_bucket_result);
             if continuation_token.is_none() {
                 break;
@@ -160,6 +164,59 @@ impl Bucket {
         Ok(results)
     }
 
+    /// Lazily list the objects of an S3 bucket, requesting the next page
+    /// once the objects of the previous one were consumed. Keys rolled up
+    /// into common prefixes by `delimiter` are not part of the stream.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    /// use futures::TryStreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let mut objects = std::pin::pin!(bucket.list_stream("logs/".to_string(), None));
+    /// while let Some(object) = objects.try_next().await? {
+    ///     println!("{} {}", object.key, object.size);
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_stream(
+        &self,
+        prefix: String,
+        delimiter: Option<String>,
+    ) -> impl Stream<Item = Result<Object, S3Error>> + '_ {
+        // `None` once the last page was listed
+        let pages = futures::stream::try_unfold(Some(None), move |continuation_token| {
+            let prefix = prefix.clone();
+            let delimiter = delimiter.clone();
+            async move {
+                let Some(continuation_token) = continuation_token else {
+                    return Ok::<_, S3Error>(None);
+                };
+                let (list_bucket_result, _) = self
+                    .list_page(prefix, delimiter, continuation_token, None, None)
+                    .await?;
+                let next = list_bucket_result.next_page_token().map(Some);
+                Ok(Some((list_bucket_result.contents, next)))
+            }
+        });
+        pages
+            .map_ok(|contents| futures::stream::iter(contents.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
     pub async fn list_multiparts_uploads_page(
         &self,
         prefix: Option<&str>,
@@ -228,6 +285,185 @@ impl Bucket {
 
         Ok(results)
     }
+
+    /// Lazily list the ongoing multipart uploads of an S3 bucket, see
+    /// [`Bucket::list_multiparts_uploads`] and [`Bucket::list_stream`].
+    pub fn list_multiparts_uploads_stream<'a>(
+        &'a self,
+        prefix: Option<&'a str>,
+        delimiter: Option<&'a str>,
+    ) -> impl Stream<Item = Result<MultipartUpload, S3Error>> + 'a {
+        // `None` once the last page was listed
+        let pages = futures::stream::try_unfold(Some(None), move |next_marker| async move {
+            let Some(next_marker) = next_marker else {
+                return Ok::<_, S3Error>(None);
+            };
+            let (list_multiparts_uploads_result, _) = self
+                .list_multiparts_uploads_page(prefix, delimiter, next_marker, None)
+                .await?;
+            // Without a marker the next request would start over from the first page.
+            let next = list_multiparts_uploads_result
+                .next_marker
+                .filter(|_| list_multiparts_uploads_result.is_truncated)
+                .map(Some);
+            Ok(Some((list_multiparts_uploads_result.uploads, next)))
+        });
+        pages
+            .map_ok(|uploads| futures::stream::iter(uploads.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    pub async fn list_object_versions_page(
+        &self,
+        prefix: String,
+        delimiter: Option<String>,
+        key_marker: Option<String>,
+        version_id_marker: Option<String>,
+        max_keys: Option<usize>,
+    ) -> Result<(ListVersionsResult, u16), S3Error> {
+        let command = Command::ListObjectVersions {
+            prefix,
+            delimiter,
+            key_marker,
+            version_id_marker,
+            max_keys,
+        };
+        let request = RequestImpl::new(self, "/", command)?;
+        let response_data = request.response_data(false).await?;
+        let list_versions_result = quick_xml::de::from_reader(response_data.as_slice())?;
+
+        Ok((list_versions_result, response_data.status_code()))
+    }
+
+    /// List the versions and delete markers of the objects in an S3 bucket.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// let results = bucket.list_object_versions("/".to_string(), Some("/".to_string())).await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_object_versions(
+        &self,
+        prefix: String,
+        delimiter: Option<String>,
+    ) -> Result<Vec<ListVersionsResult>, S3Error> {
+        let mut results = Vec::new();
+        let mut key_marker = None;
+        let mut version_id_marker = None;
+
+        loop {
+            let (list_versions_result, _) = self
+                .list_object_versions_page(
+                    prefix.clone(),
+                    delimiter.clone(),
+                    key_marker,
+                    version_id_marker,
+                    None,
+                )
+                .await?;
+
+            let is_truncated = list_versions_result.is_truncated;
+            key_marker = list_versions_result.next_key_marker.clone();
+            version_id_marker = list_versions_result.next_version_id_marker.clone();
+            results.push(list_versions_result);
+
+            // Without a key marker the next request would start over from the first page.
+            if !is_truncated || key_marker.is_none() {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
+    pub async fn list_parts_page(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number_marker: Option<u32>,
+        max_parts: Option<u32>,
+    ) -> Result<(ListPartsResult, u16), S3Error> {
+        let command = Command::ListParts {
+            upload_id,
+            part_number_marker,
+            max_parts,
+        };
+        let request = RequestImpl::new(self, path, command)?;
+        let response_data = request.response_data(false).await?;
+        let list_parts_result = quick_xml::de::from_reader(response_data.as_slice())?;
+
+        Ok((list_parts_result, response_data.status_code()))
+    }
+
+    /// List the parts uploaded so far to the multipart upload `upload_id`. This may be useful to
+    /// resume or audit an upload, together with [`crate::bucket::Bucket::list_multiparts_uploads`].
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use anyhow::Result;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse()?;
+    /// let credentials = Credentials::default()?;
+    /// let bucket = Bucket::new(bucket_name, region, credentials)?;
+    ///
+    /// for uploads in bucket.list_multiparts_uploads(None, None).await? {
+    ///     for upload in uploads.uploads {
+    ///         let results = bucket.list_parts(&upload.key, &upload.id).await?;
+    ///     }
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_parts(
+        &self,
+        path: &str,
+        upload_id: &str,
+    ) -> Result<Vec<ListPartsResult>, S3Error> {
+        let mut results = Vec::new();
+        let mut part_number_marker = None;
+
+        loop {
+            let (list_parts_result, _) = self
+                .list_parts_page(path, upload_id, part_number_marker, None)
+                .await?;
+
+            let next_part_number_marker = list_parts_result
+                .next_part_number_marker
+                .filter(|_| list_parts_result.is_truncated);
+            results.push(list_parts_result);
+
+            // Without a marker the next request would start over from the first part.
+            match next_part_number_marker {
+                Some(marker) => part_number_marker = Some(marker),
+                None => break,
+            }
+        }
+
+        Ok(results)
+    }
 }
 
 #[derive(Clone, Default, Deserialize, Debug)]
@@ -267,6 +503,11 @@ pub struct BucketContainer {
 
 #[cfg(test)]
 mod tests {
+    use futures::TryStreamExt;
+
+    use crate::transport::{memory_bucket, InMemoryTransport};
+    use crate::Bucket;
+
     #[test]
     pub fn parse_list_buckets_response() {
         let response = r#"
@@ -338,4 +579,302 @@ mod tests {
         );
         assert_eq!(parsed.buckets.bucket.len(), 0);
     }
+
+    #[tokio::test]
+    async fn list_parts_pages() {
+        let page = |marker: u32, next: u32, truncated: bool| {
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+                <ListPartsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+                    <Bucket>examplebucket</Bucket>
+                    <Key>test.file</Key>
+                    <UploadId>upload</UploadId>
+                    <PartNumberMarker>{marker}</PartNumberMarker>
+                    <NextPartNumberMarker>{next}</NextPartNumberMarker>
+                    <MaxParts>1</MaxParts>
+                    <IsTruncated>{truncated}</IsTruncated>
+                    <StorageClass>STANDARD</StorageClass>
+                    <Part>
+                        <PartNumber>{next}</PartNumber>
+                        <LastModified>2024-01-01T00:00:00.000Z</LastModified>
+                        <ETag>"etag-{next}"</ETag>
+                        <Size>5242880</Size>
+                    </Part>
+                </ListPartsResult>"#
+            )
+        };
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(page(0, 1, true).into()));
+        transport.push_response(http::Response::new(page(1, 2, false).into()));
+        let bucket = memory_bucket(&transport);
+
+        let results = bucket.list_parts("/test.file", "upload").await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].storage_class.as_deref(), Some("STANDARD"));
+        assert_eq!(results[1].part_number_marker, Some(1));
+        let parts: Vec<_> = results
+            .iter()
+            .flat_map(|result| &result.parts)
+            .map(|part| (part.part_number, part.etag.as_str(), part.size))
+            .collect();
+        assert_eq!(
+            parts,
+            [(1, "\"etag-1\"", 5242880), (2, "\"etag-2\"", 5242880)]
+        );
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].uri.query(), Some("uploadId=upload"));
+        assert_eq!(
+            requests[1].uri.query(),
+            Some("uploadId=upload&part-number-marker=1")
+        );
+    }
+
+    #[tokio::test]
+    async fn list_parts_stops_without_marker() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(
+            r#"<ListPartsResult>
+                <Bucket>examplebucket</Bucket>
+                <Key>test.file</Key>
+                <UploadId>upload</UploadId>
+                <IsTruncated>true</IsTruncated>
+            </ListPartsResult>"#
+                .into(),
+        ));
+        let bucket = memory_bucket(&transport);
+
+        let results = bucket.list_parts("/test.file", "upload").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn list_object_versions_stops_without_marker() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(
+            r#"<ListVersionsResult><Name>examplebucket</Name><IsTruncated>true</IsTruncated></ListVersionsResult>"#
+                .into(),
+        ));
+        let bucket = memory_bucket(&transport);
+
+        let results = bucket
+            .list_object_versions("photos/".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn list_object_versions_pages() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ListVersionsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+                <Name>examplebucket</Name>
+                <Prefix>photos/</Prefix>
+                <KeyMarker></KeyMarker>
+                <VersionIdMarker></VersionIdMarker>
+                <NextKeyMarker>photos/b.jpg</NextKeyMarker>
+                <NextVersionIdMarker>v3</NextVersionIdMarker>
+                <MaxKeys>3</MaxKeys>
+                <IsTruncated>true</IsTruncated>
+                <Version>
+                    <Key>photos/a.jpg</Key>
+                    <VersionId>v1</VersionId>
+                    <IsLatest>true</IsLatest>
+                    <LastModified>2024-01-02T00:00:00.000Z</LastModified>
+                    <ETag>"etag-1"</ETag>
+                    <Size>434234</Size>
+                    <StorageClass>STANDARD</StorageClass>
+                </Version>
+                <DeleteMarker>
+                    <Key>photos/b.jpg</Key>
+                    <VersionId>v2</VersionId>
+                    <IsLatest>true</IsLatest>
+                    <LastModified>2024-01-03T00:00:00.000Z</LastModified>
+                </DeleteMarker>
+                <Version>
+                    <Key>photos/b.jpg</Key>
+                    <VersionId>v3</VersionId>
+                    <IsLatest>false</IsLatest>
+                    <LastModified>2024-01-01T00:00:00.000Z</LastModified>
+                    <ETag>"etag-3"</ETag>
+                    <Size>166047</Size>
+                    <StorageClass>STANDARD</StorageClass>
+                </Version>
+            </ListVersionsResult>"#
+                .into(),
+        ));
+        transport.push_response(http::Response::new(
+            r#"<ListVersionsResult><Name>examplebucket</Name><IsTruncated>false</IsTruncated></ListVersionsResult>"#
+                .into(),
+        ));
+        let bucket = memory_bucket(&transport);
+
+        let results = bucket
+            .list_object_versions("photos/".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        let versions: Vec<_> = results[0]
+            .versions
+            .iter()
+            .map(|version| {
+                (
+                    version.key.as_str(),
+                    version.version_id.as_str(),
+                    version.is_latest,
+                )
+            })
+            .collect();
+        assert_eq!(
+            versions,
+            [("photos/a.jpg", "v1", true), ("photos/b.jpg", "v3", false)]
+        );
+        assert_eq!(results[0].delete_markers.len(), 1);
+        assert_eq!(results[0].delete_markers[0].version_id, "v2");
+        assert!(results[1].versions.is_empty());
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].uri.query(), Some("versions&prefix=photos%2F"));
+        assert_eq!(
+            requests[1].uri.query(),
+            Some("versions&prefix=photos%2F&key-marker=photos%2Fb.jpg&version-id-marker=v3")
+        );
+    }
+
+    fn objects_page(keys: &[&str], truncated: bool, next_token: Option<&str>) -> String {
+        let contents: String = keys
+            .iter()
+            .map(|key| {
+                format!(
+                    "<Contents><Key>{}</Key><LastModified>2024-01-01T00:00:00.000Z</LastModified>\
+                     <Size>1</Size></Contents>",
+                    key
+                )
+            })
+            .collect();
+        let next_token = next_token
+            .map(|token| format!("<NextContinuationToken>{}</NextContinuationToken>", token))
+            .unwrap_or_default();
+        format!(
+            "<ListBucketResult><Name>examplebucket</Name><IsTruncated>{}</IsTruncated>{}{}\
+             </ListBucketResult>",
+            truncated, next_token, contents
+        )
+    }
+
+    #[tokio::test]
+    async fn list_stream_pages_lazily() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(
+            objects_page(&["a", "b"], true, Some("token")).into(),
+        ));
+        transport.push_response(http::Response::new(
+            objects_page(&["c"], false, None).into(),
+        ));
+        let bucket = memory_bucket(&transport);
+
+        let mut objects = std::pin::pin!(bucket.list_stream("".to_string(), None));
+        assert_eq!(objects.try_next().await.unwrap().unwrap().key, "a");
+        assert_eq!(transport.requests().len(), 1);
+
+        let rest: Vec<_> = objects
+            .map_ok(|object| object.key)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rest, ["b", "c"]);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1]
+            .uri
+            .query()
+            .unwrap()
+            .contains("continuation-token=token"));
+    }
+
+    #[tokio::test]
+    async fn list_stream_v1_uses_last_key() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(
+            objects_page(&["a", "b"], true, None).into(),
+        ));
+        transport.push_response(http::Response::new(
+            objects_page(&["c"], false, None).into(),
+        ));
+        let bucket = Bucket::builder("examplebucket", "us-east-1".parse().unwrap())
+            .listobjects_v1()
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let keys: Vec<_> = bucket
+            .list_stream("".to_string(), None)
+            .map_ok(|object| object.key)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(keys, ["a", "b", "c"]);
+        assert_eq!(
+            transport.requests()[1].uri.query(),
+            Some("prefix=&marker=b")
+        );
+    }
+
+    #[tokio::test]
+    async fn list_multiparts_uploads_stream_pages() {
+        let page = |key: &str, truncated: bool| {
+            format!(
+                "<ListMultipartUploadsResult><Bucket>examplebucket</Bucket>\
+                 <NextKeyMarker>{key}</NextKeyMarker><IsTruncated>{truncated}</IsTruncated>\
+                 <Upload><Key>{key}</Key><UploadId>upload-{key}</UploadId>\
+                 <Initiated>2024-01-01T00:00:00.000Z</Initiated>\
+                 <StorageClass>STANDARD</StorageClass></Upload>\
+                 </ListMultipartUploadsResult>"
+            )
+        };
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(page("a", true).into()));
+        transport.push_response(http::Response::new(page("b", false).into()));
+        let bucket = memory_bucket(&transport);
+
+        let ids: Vec<_> = bucket
+            .list_multiparts_uploads_stream(None, None)
+            .map_ok(|upload| upload.id)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids, ["upload-a", "upload-b"]);
+        assert_eq!(
+            transport.requests()[1].uri.query(),
+            Some("uploads&key-marker=a")
+        );
+    }
+
+    #[tokio::test]
+    async fn list_multiparts_uploads_stream_stops_without_marker() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(http::Response::new(
+            "<ListMultipartUploadsResult><Bucket>examplebucket</Bucket>\
+             <IsTruncated>true</IsTruncated>\
+             <Upload><Key>a</Key><UploadId>upload-a</UploadId>\
+             <Initiated>2024-01-01T00:00:00.000Z</Initiated>\
+             <StorageClass>STANDARD</StorageClass></Upload>\
+             </ListMultipartUploadsResult>"
+                .into(),
+        ));
+        let bucket = memory_bucket(&transport);
+
+        let ids: Vec<_> = bucket
+            .list_multiparts_uploads_stream(None, None)
+            .map_ok(|upload| upload.id)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids, ["upload-a"]);
+        assert_eq!(transport.requests().len(), 1);
+    }
 }
@@ -1,17 +1,15 @@
 mod presign;
 use crate::error::S3Error;
-use awscreds::Credentials;
+use crate::multipart::MultipartConfig;
+use crate::retry::RetryPolicy;
+use crate::transport::HttpTransport;
+use awscreds::{Credentials, CredentialsProvider};
 use awsregion::Region;
-use bytes::Bytes;
 use http::HeaderMap;
-use http_body_util::Full;
-use hyper_timeout::TimeoutConnector;
-use hyper_util::client::legacy::connect::HttpConnector;
-use hyper_util::client::legacy::Client;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 pub use std::io::Read;
 
@@ -39,12 +37,19 @@ mod head;
 mod utils;
 
 mod client;
+pub use client::HttpClient;
+
+mod builder;
+pub use builder::{validate_bucket_name, BucketBuilder};
 
 pub type Query = HashMap<String, String>;
 
 pub use crate::serde_types::{
-    BucketLocationResult, CompleteMultipartUploadData, CorsConfiguration, HeadObjectResult,
-    InitiateMultipartUploadResponse, ListBucketResult, ListMultipartUploadsResult, Part,
+    BucketLocationResult, CompleteMultipartUploadData, CopyObjectResult, CopyPartResult,
+    CorsConfiguration, DeleteError, DeleteObjectsData, DeleteObjectsResult, DeletedObject,
+    HeadObjectResult, InitiateMultipartUploadResponse, ListBucketResult,
+    ListMultipartUploadsResult, ListPartsResult, ListVersionsResult, ObjectIdentifier, Part,
+    UploadedPart, VersioningConfiguration, VersioningStatus,
 };
 pub(crate) use crate::utils::error_from_response_data;
 pub use crate::utils::PutStreamResponse;
@@ -55,6 +60,8 @@ pub const CHUNK_SIZE: usize = 8_388_608; // 8 Mebibytes, min is 5 (5_242_880);
 
 /// Instantiate an existing Bucket
 ///
+/// Use [`Bucket::builder`] for anything beyond the defaults.
+///
 /// # Example
 ///
 /// ```no_run
@@ -72,12 +79,21 @@ pub struct Bucket {
     pub name: String,
     pub region: Region,
     pub credentials: Arc<RwLock<Credentials>>,
+    credentials_provider: Option<Arc<dyn CredentialsProvider>>,
+    credentials_refresh_lock: Arc<Mutex<()>>,
     pub extra_headers: HeaderMap,
     pub extra_query: Query,
     pub request_timeout: Option<Duration>,
     path_style: bool,
     listobjects_v2: bool,
-    http_client: Client<TimeoutConnector<HttpConnector>, Full<Bytes>>,
+    retry_policy: RetryPolicy,
+    multipart_config: MultipartConfig,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    transport: Arc<dyn HttpTransport>,
+    /// The TLS configuration of the hyper client the bucket built itself,
+    /// `None` if it was given one or a custom transport.
+    tls_config: Option<Arc<rustls::ClientConfig>>,
 }
 
 const DEFAULT_REQUEST_TIMEOUT: Option<Duration> = Some(Duration::from_secs(60));
@@ -91,75 +107,76 @@ fn validate_expiry(expiry_secs: u32) -> Result<(), S3Error> {
 
 impl Bucket {
     pub fn with_path_style(&self) -> Self {
-        Self {
-            name: self.name.clone(),
-            region: self.region.clone(),
-            credentials: self.credentials.clone(),
-            extra_headers: self.extra_headers.clone(),
-            extra_query: self.extra_query.clone(),
-            request_timeout: self.request_timeout,
-            path_style: true,
-            listobjects_v2: self.listobjects_v2,
-            http_client: self.http_client.clone(),
-        }
+        let mut bucket = self.clone();
+        bucket.path_style = true;
+        bucket
     }
 
     pub fn with_extra_headers(&self, extra_headers: HeaderMap) -> Self {
-        Self {
-            name: self.name.clone(),
-            region: self.region.clone(),
-            credentials: self.credentials.clone(),
-            extra_headers,
-            extra_query: self.extra_query.clone(),
-            request_timeout: self.request_timeout,
-            path_style: self.path_style,
-            listobjects_v2: self.listobjects_v2,
-            http_client: self.http_client.clone(),
-        }
+        let mut bucket = self.clone();
+        bucket.extra_headers = extra_headers;
+        bucket
     }
 
     pub fn with_extra_query(&self, extra_query: HashMap<String, String>) -> Self {
-        Self {
-            name: self.name.clone(),
-            region: self.region.clone(),
-            credentials: self.credentials.clone(),
-            extra_headers: self.extra_headers.clone(),
-            extra_query,
-            request_timeout: self.request_timeout,
-            path_style: self.path_style,
-            listobjects_v2: self.listobjects_v2,
-            http_client: self.http_client.clone(),
-        }
+        let mut bucket = self.clone();
+        bucket.extra_query = extra_query;
+        bucket
     }
 
+    /// Time out requests after `request_timeout`, see
+    /// [`BucketBuilder::request_timeout`].
+    ///
+    /// The connect and read timeouts of the default hyper client are set to
+    /// `request_timeout` as well, a shared client or custom transport keeps
+    /// its own. Use [`Bucket::builder`] to set the three timeouts separately.
     pub fn with_request_timeout(&self, request_timeout: Duration) -> Result<Self, S3Error> {
-        let http_client = client::create_client(Some(request_timeout))?;
-
-        Ok(Self {
-            name: self.name.clone(),
-            region: self.region.clone(),
-            credentials: self.credentials.clone(),
-            extra_headers: self.extra_headers.clone(),
-            extra_query: self.extra_query.clone(),
-            request_timeout: Some(request_timeout),
-            path_style: self.path_style,
-            listobjects_v2: self.listobjects_v2,
-            http_client: http_client.clone(),
-        })
+        let mut bucket = self.clone();
+        bucket.request_timeout = Some(request_timeout);
+        if let Some(tls_config) = &self.tls_config {
+            bucket.connect_timeout = Some(request_timeout);
+            bucket.read_timeout = Some(request_timeout);
+            bucket.rebuild_client(tls_config.clone())?;
+        }
+        Ok(bucket)
     }
 
-    pub fn with_listobjects_v1(&self) -> Self {
-        Self {
-            name: self.name.clone(),
-            region: self.region.clone(),
-            credentials: self.credentials.clone(),
-            extra_headers: self.extra_headers.clone(),
-            extra_query: self.extra_query.clone(),
-            request_timeout: self.request_timeout,
-            path_style: self.path_style,
-            listobjects_v2: false,
-            http_client: self.http_client.clone(),
+    /// Replace the default hyper client with one using the current timeouts
+    /// and `tls_config`.
+    fn rebuild_client(&mut self, tls_config: Arc<rustls::ClientConfig>) -> Result<(), S3Error> {
+        let http_client =
+            client::create_client(self.connect_timeout, self.read_timeout, tls_config.clone())?;
+        self.transport = Arc::new(http_client);
+        self.tls_config = Some(tls_config);
+        Ok(())
+    }
+
+    /// Trust the CA certificates in `pem` for `https://` endpoints, in
+    /// addition to the webpki roots. Useful for self-hosted S3 deployments,
+    /// such as Minio, behind an internal CA.
+    ///
+    /// Only applies to the hyper client the bucket built itself, a shared
+    /// [`BucketBuilder::http_client`] or custom [`BucketBuilder::transport`]
+    /// is kept as is. The `pem` is checked either way.
+    pub fn with_ca_certificates_pem(&self, pem: &[u8]) -> Result<Self, S3Error> {
+        let tls_config = Arc::new(client::tls_config(Some(pem))?);
+        let mut bucket = self.clone();
+        if self.tls_config.is_some() {
+            bucket.rebuild_client(tls_config)?;
         }
+        Ok(bucket)
+    }
+
+    pub fn with_listobjects_v1(&self) -> Self {
+        let mut bucket = self.clone();
+        bucket.listobjects_v2 = false;
+        bucket
+    }
+
+    pub fn with_retry_policy(&self, retry_policy: RetryPolicy) -> Self {
+        let mut bucket = self.clone();
+        bucket.retry_policy = retry_policy;
+        bucket
     }
 
     pub(crate) fn _tags_xml<S: AsRef<str>>(&self, tags: &[(S, S)]) -> String {
@@ -1,6 +1,7 @@
 use crate::bucket::{validate_expiry, Bucket, Request};
 use crate::command::Command;
 use crate::error::S3Error;
+use crate::object_ops::Encryption;
 use crate::request::RequestImpl;
 use http::header::HeaderMap;
 use std::collections::HashMap;
@@ -43,6 +44,50 @@ impl Bucket {
             Command::PresignGet {
                 expiry_secs,
                 custom_queries,
+                custom_headers: None,
+            },
+        )?;
+        request.presigned()
+    }
+
+    /// Get a presigned url for getting an object encrypted with SSE-C.
+    ///
+    /// The SSE-C headers are signed, whoever uses the url has to send them
+    /// along with the same values, the key is not part of the url.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use s3::object_ops::Encryption;
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse().unwrap();
+    /// let credentials = Credentials::default().unwrap();
+    /// let bucket = Bucket::new(bucket_name, region, credentials).unwrap();
+    ///
+    /// let encryption = Encryption::CustomerKey([7; 32]);
+    /// let url = bucket.presign_get_with_encryption("/test.file", 86400, None, &encryption).unwrap();
+    /// println!("Presigned url: {}", url);
+    /// ```
+    pub fn presign_get_with_encryption<S: AsRef<str>>(
+        &self,
+        path: S,
+        expiry_secs: u32,
+        custom_queries: Option<HashMap<String, String>>,
+        encryption: &Encryption,
+    ) -> Result<String, S3Error> {
+        validate_expiry(expiry_secs)?;
+        let mut custom_headers = HeaderMap::new();
+        encryption.add_customer_key_headers(&mut custom_headers)?;
+        let request = RequestImpl::new(
+            self,
+            path.as_ref(),
+            Command::PresignGet {
+                expiry_secs,
+                custom_queries,
+                custom_headers: Some(custom_headers),
             },
         )?;
         request.presigned()
@@ -130,6 +175,39 @@ impl Bucket {
         request.presigned()
     }
 
+    /// Get a presigned url for putting an object with server-side encryption.
+    ///
+    /// The encryption headers are signed, whoever uses the url has to send
+    /// them along with the same values.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use s3::bucket::Bucket;
+    /// use s3::creds::Credentials;
+    /// use s3::object_ops::Encryption;
+    ///
+    /// let bucket_name = "rust-s3-test";
+    /// let region = "us-east-1".parse().unwrap();
+    /// let credentials = Credentials::default().unwrap();
+    /// let bucket = Bucket::new(bucket_name, region, credentials).unwrap();
+    ///
+    /// let encryption = Encryption::Kms { key_id: None };
+    /// let url = bucket.presign_put_with_encryption("/test.file", 86400, None, &encryption).unwrap();
+    /// println!("Presigned url: {}", url);
+    /// ```
+    pub fn presign_put_with_encryption<S: AsRef<str>>(
+        &self,
+        path: S,
+        expiry_secs: u32,
+        custom_headers: Option<HeaderMap>,
+        encryption: &Encryption,
+    ) -> Result<String, S3Error> {
+        let mut custom_headers = custom_headers.unwrap_or_default();
+        encryption.add_headers(&mut custom_headers)?;
+        self.presign_put(path, expiry_secs, Some(custom_headers))
+    }
+
     /// Get a presigned url for deleting object on a given path
     ///
     /// # Example:
@@ -157,3 +235,40 @@ impl Bucket {
         request.presigned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::object_ops::Encryption;
+    use crate::transport::{memory_bucket, InMemoryTransport};
+    use crate::Bucket;
+
+    fn bucket() -> Bucket {
+        memory_bucket(&InMemoryTransport::new())
+    }
+
+    #[test]
+    fn presign_signs_encryption_headers() {
+        let bucket = bucket();
+
+        let url = bucket
+            .presign_get_with_encryption(
+                "/test.file",
+                3600,
+                None,
+                &Encryption::CustomerKey([7; 32]),
+            )
+            .unwrap();
+        assert!(url.contains(
+            "X-Amz-SignedHeaders=host%3Bx-amz-server-side-encryption-customer-algorithm\
+             %3Bx-amz-server-side-encryption-customer-key\
+             %3Bx-amz-server-side-encryption-customer-key-md5"
+        ));
+        // The key is sent as a header, never as part of the url
+        assert!(!url.contains("BwcHBwcH"));
+
+        let url = bucket
+            .presign_put_with_encryption("/test.file", 3600, None, &Encryption::S3)
+            .unwrap();
+        assert!(url.contains("X-Amz-SignedHeaders=host%3Bx-amz-server-side-encryption&"));
+    }
+}
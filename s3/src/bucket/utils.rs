@@ -5,6 +5,7 @@ use http::HeaderName;
 use crate::bucket::*;
 use crate::command::Command;
 use crate::request::RequestImpl;
+use crate::retry::RetryPolicy;
 
 impl Bucket {
     /// Get path_style field of the Bucket struct
@@ -29,14 +30,40 @@ impl Bucket {
 
     /// Configure bucket to apply this request timeout to all HTTP
     /// requests, or no (infinity) timeout if `None`.  Defaults to
-    /// 30 seconds.
+    /// 60 seconds.
     ///
-    /// Only the [`hyper`] backend obeys this option;
-    /// async code may instead await with a timeout.
+    /// The timeout covers sending the request and receiving the response
+    /// headers, and applies to every attempt separately.
     pub fn set_request_timeout(&mut self, timeout: Option<Duration>) {
         self.request_timeout = timeout;
     }
 
+    /// Configure how failed requests are retried, see [`RetryPolicy`].
+    /// Use [`RetryPolicy::none`] to send every request only once.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Get the policy used to retry failed requests.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Configure how streamed uploads are split into parts, see [`MultipartConfig`].
+    pub fn set_multipart_config(&mut self, multipart_config: MultipartConfig) {
+        self.multipart_config = multipart_config;
+    }
+
+    /// Get the configuration of streamed multipart uploads.
+    pub fn multipart_config(&self) -> &MultipartConfig {
+        &self.multipart_config
+    }
+
+    /// Get the transport requests are sent through.
+    pub fn transport(&self) -> Arc<dyn HttpTransport> {
+        self.transport.clone()
+    }
+
     /// Configure bucket to use the older ListObjects API
     ///
     /// If your provider doesn't support the ListObjectsV2 interface, set this to
@@ -148,9 +175,11 @@ impl Bucket {
         self.credentials.clone()
     }
 
-    /// Change the credentials used by the Bucket.
+    /// Change the credentials used by the Bucket, replacing any credentials
+    /// provider.
     pub fn set_credentials(&mut self, credentials: Credentials) {
         self.credentials = Arc::new(RwLock::new(credentials));
+        self.credentials_provider = None;
     }
 
     /// Add an extra header to send with requests to S3.
@@ -201,6 +230,16 @@ impl Bucket {
         self.request_timeout
     }
 
+    /// Get the timeout for establishing a connection.
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// Get the timeout for a single read from or write to a connection.
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
     /// Get Bucket location.
     ///
     /// # Example:
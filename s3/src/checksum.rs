@@ -0,0 +1,154 @@
+//! Additional object checksums, sent as `x-amz-checksum-*` headers or trailers.
+
+use std::fmt;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+/// The algorithms S3 supports for additional checksums.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChecksumAlgorithm {
+    Crc32,
+    Crc32c,
+    Sha1,
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    /// The header, or trailer, carrying a checksum, e.g. `x-amz-checksum-crc32c`.
+    pub fn header_name(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Crc32 => "x-amz-checksum-crc32",
+            ChecksumAlgorithm::Crc32c => "x-amz-checksum-crc32c",
+            ChecksumAlgorithm::Sha1 => "x-amz-checksum-sha1",
+            ChecksumAlgorithm::Sha256 => "x-amz-checksum-sha256",
+        }
+    }
+
+    /// Start computing a checksum incrementally.
+    pub fn hasher(&self) -> Checksum {
+        let state = match self {
+            ChecksumAlgorithm::Crc32 => State::Crc32(crc32fast::Hasher::new()),
+            ChecksumAlgorithm::Crc32c => State::Crc32c(0),
+            ChecksumAlgorithm::Sha1 => State::Sha1(Sha1::new()),
+            ChecksumAlgorithm::Sha256 => State::Sha256(Sha256::new()),
+        };
+        Checksum { state }
+    }
+
+    /// The base64 encoded checksum of `data`, as S3 expects it.
+    pub fn checksum(&self, data: &[u8]) -> String {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Length of a base64 encoded checksum.
+    pub(crate) fn encoded_len(&self) -> usize {
+        match self {
+            ChecksumAlgorithm::Crc32 | ChecksumAlgorithm::Crc32c => 8,
+            ChecksumAlgorithm::Sha1 => 28,
+            ChecksumAlgorithm::Sha256 => 44,
+        }
+    }
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChecksumAlgorithm::Crc32 => write!(f, "CRC32"),
+            ChecksumAlgorithm::Crc32c => write!(f, "CRC32C"),
+            ChecksumAlgorithm::Sha1 => write!(f, "SHA1"),
+            ChecksumAlgorithm::Sha256 => write!(f, "SHA256"),
+        }
+    }
+}
+
+/// A checksum being computed, see [`ChecksumAlgorithm::hasher`].
+#[derive(Clone)]
+pub struct Checksum {
+    state: State,
+}
+
+#[derive(Clone)]
+enum State {
+    Crc32(crc32fast::Hasher),
+    Crc32c(u32),
+    Sha1(Sha1),
+    Sha256(Sha256),
+}
+
+impl Checksum {
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            State::Crc32(hasher) => hasher.update(data),
+            State::Crc32c(crc) => *crc = crc32c::crc32c_append(*crc, data),
+            State::Sha1(hasher) => hasher.update(data),
+            State::Sha256(hasher) => hasher.update(data),
+        }
+    }
+
+    /// The base64 encoded checksum of everything passed to [`update`](Self::update).
+    pub fn finalize(self) -> String {
+        let digest = match self.state {
+            State::Crc32(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            State::Crc32c(crc) => crc.to_be_bytes().to_vec(),
+            State::Sha1(hasher) => hasher.finalize().to_vec(),
+            State::Sha256(hasher) => hasher.finalize().to_vec(),
+        };
+        general_purpose::STANDARD.encode(digest)
+    }
+}
+
+impl fmt::Debug for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let algorithm = match self.state {
+            State::Crc32(_) => ChecksumAlgorithm::Crc32,
+            State::Crc32c(_) => ChecksumAlgorithm::Crc32c,
+            State::Sha1(_) => ChecksumAlgorithm::Sha1,
+            State::Sha256(_) => ChecksumAlgorithm::Sha256,
+        };
+        f.debug_struct("Checksum")
+            .field("algorithm", &algorithm)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChecksumAlgorithm;
+
+    #[test]
+    fn checksums() {
+        let data = b"Hello world";
+        assert_eq!(ChecksumAlgorithm::Crc32.checksum(data), "i9aeUg==");
+        assert_eq!(ChecksumAlgorithm::Crc32c.checksum(data), "crUfeA==");
+        assert_eq!(
+            ChecksumAlgorithm::Sha1.checksum(data),
+            "e1AsOh9IyGCa4hLN+2Od7jlnP14="
+        );
+        assert_eq!(
+            ChecksumAlgorithm::Sha256.checksum(data),
+            "ZOyIygCyaOW6GjVnihtTFtIS9PNmskdyMlNKiuyjfzw="
+        );
+    }
+
+    #[test]
+    fn incremental_matches_whole() {
+        for algorithm in [
+            ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::Sha1,
+            ChecksumAlgorithm::Sha256,
+        ] {
+            let mut hasher = algorithm.hasher();
+            hasher.update(b"Hello");
+            hasher.update(b" world");
+            let checksum = hasher.finalize();
+            assert_eq!(checksum, algorithm.checksum(b"Hello world"));
+            assert_eq!(checksum.len(), algorithm.encoded_len());
+        }
+    }
+}
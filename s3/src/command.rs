@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
-use crate::serde_types::{CompleteMultipartUploadData, CorsConfiguration};
+use crate::serde_types::{
+    CompleteMultipartUploadData, CorsConfiguration, DeleteObjectsData, VersioningConfiguration,
+};
 
+use crate::body::PutBody;
+use crate::checksum::ChecksumAlgorithm;
+use crate::error::S3Error;
 use crate::EMPTY_PAYLOAD_SHA;
 use sha2::{Digest, Sha256};
 
@@ -27,12 +32,14 @@ impl fmt::Display for HttpMethod {
     }
 }
 use crate::bucket_ops::BucketConfiguration;
-use http::HeaderMap;
+use crate::object_ops::{Conditions, CopyObjectOptions, Encryption, PutObjectOptions};
+use http::{HeaderMap, HeaderName};
 
 #[derive(Clone, Debug)]
 pub struct Multipart<'a> {
     part_number: u32,
     upload_id: &'a str,
+    checksum: Option<(ChecksumAlgorithm, String)>,
 }
 
 impl<'a> Multipart<'a> {
@@ -47,19 +54,61 @@ impl<'a> Multipart<'a> {
         Multipart {
             part_number,
             upload_id,
+            checksum: None,
         }
     }
+
+    /// Send the part along with its `checksum`.
+    pub fn with_checksum(mut self, algorithm: ChecksumAlgorithm, checksum: String) -> Self {
+        self.checksum = Some((algorithm, checksum));
+        self
+    }
+
+    pub fn add_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        if let Some((algorithm, checksum)) = &self.checksum {
+            headers.insert(
+                HeaderName::from_static(algorithm.header_name()),
+                checksum.parse()?,
+            );
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, strum_macros::Display)]
 pub enum Command<'a> {
     HeadObject,
+    /// `HeadObject` with preconditions or an SSE-C key.
+    HeadObjectWithOptions {
+        conditions: Option<&'a Conditions>,
+        encryption: Option<&'a Encryption>,
+    },
+    HeadObjectVersion {
+        version_id: &'a str,
+    },
     CopyObject {
         from: &'a str,
+        options: &'a CopyObjectOptions,
     },
     DeleteObject,
+    DeleteObjectVersion {
+        version_id: &'a str,
+    },
+    DeleteObjects {
+        data: DeleteObjectsData,
+    },
     DeleteObjectTagging,
     GetObject,
+    /// `GetObject` with preconditions, an SSE-C key or the checksum mode.
+    GetObjectWithOptions {
+        conditions: Option<&'a Conditions>,
+        encryption: Option<&'a Encryption>,
+        /// Ask for the additional checksum of the object, if it has one.
+        checksum_mode: bool,
+    },
+    GetObjectVersion {
+        version_id: &'a str,
+    },
     GetObjectTorrent,
     GetObjectRange {
         start: u64,
@@ -67,9 +116,11 @@ pub enum Command<'a> {
     },
     GetObjectTagging,
     PutObject {
-        content: &'a [u8],
+        content: PutBody,
         content_type: &'a str,
         multipart: Option<Multipart<'a>>,
+        conditions: Option<&'a Conditions>,
+        options: Option<&'a PutObjectOptions>,
     },
     PutObjectTagging {
         tags: &'a str,
@@ -80,6 +131,11 @@ pub enum Command<'a> {
         key_marker: Option<String>,
         max_uploads: Option<usize>,
     },
+    ListParts {
+        upload_id: &'a str,
+        part_number_marker: Option<u32>,
+        max_parts: Option<u32>,
+    },
     ListObjects {
         prefix: String,
         delimiter: Option<String>,
@@ -93,10 +149,18 @@ pub enum Command<'a> {
         start_after: Option<String>,
         max_keys: Option<usize>,
     },
+    ListObjectVersions {
+        prefix: String,
+        delimiter: Option<String>,
+        key_marker: Option<String>,
+        version_id_marker: Option<String>,
+        max_keys: Option<usize>,
+    },
     GetBucketLocation,
     PresignGet {
         expiry_secs: u32,
         custom_queries: Option<HashMap<String, String>>,
+        custom_headers: Option<HeaderMap>,
     },
     PresignPut {
         expiry_secs: u32,
@@ -111,12 +175,20 @@ pub enum Command<'a> {
     },
     InitiateMultipartUpload {
         content_type: &'a str,
+        options: Option<&'a PutObjectOptions>,
     },
     UploadPart {
         part_number: u32,
         content: &'a [u8],
         upload_id: &'a str,
     },
+    UploadPartCopy {
+        from: &'a str,
+        multipart: Multipart<'a>,
+        start: u64,
+        end: u64,
+        options: &'a CopyObjectOptions,
+    },
     AbortMultipartUpload {
         upload_id: &'a str,
     },
@@ -132,12 +204,20 @@ pub enum Command<'a> {
     PutBucketCors {
         configuration: CorsConfiguration,
     },
+    GetBucketVersioning,
+    PutBucketVersioning {
+        configuration: VersioningConfiguration,
+    },
 }
 
 impl<'a> Command<'a> {
     pub fn http_verb(&self) -> HttpMethod {
         match *self {
             Command::GetObject
+            | Command::GetObjectWithOptions { .. }
+            | Command::GetObjectVersion { .. }
+            | Command::ListObjectVersions { .. }
+            | Command::GetBucketVersioning
             | Command::GetObjectTorrent
             | Command::GetObjectRange { .. }
             | Command::ListBuckets
@@ -146,34 +226,42 @@ impl<'a> Command<'a> {
             | Command::GetBucketLocation
             | Command::GetObjectTagging
             | Command::ListMultipartUploads { .. }
+            | Command::ListParts { .. }
             | Command::PresignGet { .. } => HttpMethod::Get,
             Command::PutObject { .. }
-            | Command::CopyObject { from: _ }
+            | Command::CopyObject { .. }
             | Command::PutObjectTagging { .. }
             | Command::PresignPut { .. }
             | Command::UploadPart { .. }
+            | Command::UploadPartCopy { .. }
             | Command::PutBucketCors { .. }
+            | Command::PutBucketVersioning { .. }
             | Command::CreateBucket { .. } => HttpMethod::Put,
             Command::DeleteObject
+            | Command::DeleteObjectVersion { .. }
             | Command::DeleteObjectTagging
             | Command::AbortMultipartUpload { .. }
             | Command::PresignDelete { .. }
             | Command::DeleteBucket => HttpMethod::Delete,
-            Command::InitiateMultipartUpload { .. } | Command::CompleteMultipartUpload { .. } => {
-                HttpMethod::Post
-            }
-            Command::HeadObject => HttpMethod::Head,
+            Command::InitiateMultipartUpload { .. }
+            | Command::CompleteMultipartUpload { .. }
+            | Command::DeleteObjects { .. } => HttpMethod::Post,
+            Command::HeadObject
+            | Command::HeadObjectWithOptions { .. }
+            | Command::HeadObjectVersion { .. } => HttpMethod::Head,
             Command::PresignPost { .. } => HttpMethod::Post,
         }
     }
 
-    pub fn content_length(&self) -> usize {
-        match &self {
-            Command::CopyObject { from: _ } => 0,
-            Command::PutObject { content, .. } => content.len(),
+    pub fn content_length(&self) -> u64 {
+        let len = match &self {
+            Command::CopyObject { .. } => 0,
+            Command::PutObject { content, .. } => return content.encoded_len(),
             Command::PutObjectTagging { tags } => tags.len(),
             Command::UploadPart { content, .. } => content.len(),
             Command::CompleteMultipartUpload { data, .. } => data.len(),
+            Command::PutBucketVersioning { configuration } => configuration.to_string().len(),
+            Command::DeleteObjects { data } => data.to_string().len(),
             Command::CreateBucket { config } => {
                 if let Some(payload) = config.location_constraint_payload() {
                     Vec::from(payload).len()
@@ -182,25 +270,24 @@ impl<'a> Command<'a> {
                 }
             }
             _ => 0,
-        }
+        };
+        len as u64
     }
 
     pub fn content_type(&self) -> String {
         match self {
-            Command::InitiateMultipartUpload { content_type } => content_type.to_string(),
+            Command::InitiateMultipartUpload { content_type, .. } => content_type.to_string(),
             Command::PutObject { content_type, .. } => content_type.to_string(),
-            Command::CompleteMultipartUpload { .. } => "application/xml".into(),
+            Command::CompleteMultipartUpload { .. }
+            | Command::PutBucketVersioning { .. }
+            | Command::DeleteObjects { .. } => "application/xml".into(),
             _ => "text/plain".into(),
         }
     }
 
     pub fn sha256(&self) -> String {
         match &self {
-            Command::PutObject { content, .. } => {
-                let mut sha = Sha256::default();
-                sha.update(content);
-                hex::encode(sha.finalize().as_slice())
-            }
+            Command::PutObject { content, .. } => content.content_sha256(),
             Command::PutObjectTagging { tags } => {
                 let mut sha = Sha256::default();
                 sha.update(tags.as_bytes());
@@ -211,6 +298,16 @@ impl<'a> Command<'a> {
                 sha.update(data.to_string().as_bytes());
                 hex::encode(sha.finalize().as_slice())
             }
+            Command::PutBucketVersioning { configuration } => {
+                let mut sha = Sha256::default();
+                sha.update(configuration.to_string().as_bytes());
+                hex::encode(sha.finalize().as_slice())
+            }
+            Command::DeleteObjects { data } => {
+                let mut sha = Sha256::default();
+                sha.update(data.to_string().as_bytes());
+                hex::encode(sha.finalize().as_slice())
+            }
             Command::CreateBucket { config } => {
                 if let Some(payload) = config.location_constraint_payload() {
                     let mut sha = Sha256::default();
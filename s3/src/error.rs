@@ -8,6 +8,8 @@ pub enum S3Error {
     MaxExpiry(u32),
     #[error("Got HTTP {0} with content '{1}'")]
     HttpFailWithBody(u16, String),
+    #[error("{0}")]
+    Service(Box<ServiceError>),
     #[error("Http request returned a non 2** code")]
     HttpFail,
     #[error("aws-creds: {0}")]
@@ -24,6 +26,10 @@ pub enum S3Error {
     Http(#[from] http::Error),
     #[error("hyper: {0}")]
     Hyper(#[from] hyper::Error),
+    #[error("rustls: {0}")]
+    Rustls(#[from] rustls::Error),
+    #[error("No certificates found in PEM bundle")]
+    PemNoCertificates,
     #[error("hyperUtil: {0}")]
     HyperUtil(#[from] hyper_util::client::legacy::Error),
     #[error("header to string: {0}")]
@@ -32,6 +38,8 @@ pub enum S3Error {
     FromUtf8(#[from] std::string::FromUtf8Error),
     #[error("serde xml: {0}")]
     SerdeXml(#[from] quick_xml::de::DeError),
+    #[error("serde json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
     #[error("invalid header value: {0}")]
     InvalidHeaderValue(#[from] http::header::InvalidHeaderValue),
     #[error("invalid header name: {0}")]
@@ -44,4 +52,140 @@ pub enum S3Error {
     TimeFormatError(#[from] time::error::Format),
     #[error("fmt error: {0}")]
     FmtError(#[from] std::fmt::Error),
+    #[error("Invalid bucket name '{name}': {reason}")]
+    InvalidBucketName {
+        name: String,
+        reason: BucketNameError,
+    },
+    #[error("Request timed out")]
+    Timeout(#[from] tokio::time::error::Elapsed),
+    #[error("Streamed request body was already sent and can not be sent again")]
+    BodyConsumed,
+    #[error("Signed chunks can not be sent without credentials")]
+    ChunkSigningWithoutCredentials,
+    #[error("Multipart uploads are limited to 10000 parts")]
+    TooManyParts,
+    #[error("join: {0}")]
+    Join(#[from] tokio::task::JoinError),
+    #[error("{algorithm} checksum mismatch, expected {expected}, computed {computed}")]
+    ChecksumMismatch {
+        algorithm: crate::checksum::ChecksumAlgorithm,
+        expected: String,
+        computed: String,
+    },
+}
+
+/// Why a bucket name breaks the S3 naming rules.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketNameError {
+    #[error("must be between 3 and 63 characters long, got {0}")]
+    Length(usize),
+    #[error(
+        "invalid character '{0}', only lowercase letters, numbers, dots and hyphens are allowed"
+    )]
+    InvalidCharacter(char),
+    #[error("must begin and end with a letter or number")]
+    InvalidBoundary,
+    #[error("must not contain two adjacent periods")]
+    AdjacentPeriods,
+    #[error("must not be formatted as an IP address")]
+    IpAddress,
+    #[error("must not start with the reserved prefix '{0}'")]
+    ReservedPrefix(&'static str),
+    #[error("must not end with the reserved suffix '{0}'")]
+    ReservedSuffix(&'static str),
+}
+
+/// An error returned by the S3 service, parsed from the XML error body.
+///
+/// Responses without a body, such as the ones to `HEAD` requests, get a
+/// `code` derived from the HTTP status, e.g. `NotFound` for a 404.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("S3 returned HTTP {status} {code}: {message}")]
+pub struct ServiceError {
+    /// The HTTP status of the response.
+    pub status: u16,
+    /// The S3 error code, e.g. `NoSuchKey` or `SlowDown`.
+    pub code: String,
+    /// A human readable description of the error.
+    pub message: String,
+    pub request_id: Option<String>,
+    pub host_id: Option<String>,
+    /// The bucket or object the error applies to.
+    pub resource: Option<String>,
+}
+
+impl ServiceError {
+    pub fn is_not_found(&self) -> bool {
+        self.status == 404
+            || matches!(
+                self.code.as_str(),
+                "NoSuchKey" | "NoSuchBucket" | "NoSuchUpload" | "NoSuchVersion" | "NotFound"
+            )
+    }
+
+    /// Whether S3 denied access, other 403 codes such as
+    /// `SignatureDoesNotMatch` or `RequestTimeTooSkewed` are not included.
+    /// Only a 403 without an error body, which has the `Forbidden` code
+    /// derived from the status, counts by its status alone.
+    pub fn is_access_denied(&self) -> bool {
+        self.code == "AccessDenied" || (self.status == 403 && self.code == "Forbidden")
+    }
+
+    pub fn is_precondition_failed(&self) -> bool {
+        self.status == 412 || self.code == "PreconditionFailed"
+    }
+
+    pub fn is_not_modified(&self) -> bool {
+        self.status == 304
+    }
+}
+
+impl S3Error {
+    /// The parsed service error, if S3 answered with one.
+    pub fn as_service_error(&self) -> Option<&ServiceError> {
+        match self {
+            S3Error::Service(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// The HTTP status of a failed response, if the error came from one.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            S3Error::Service(error) => Some(error.status),
+            S3Error::HttpFailWithBody(status, _) => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// The S3 error code, e.g. `NoSuchKey`, if S3 answered with one.
+    pub fn code(&self) -> Option<&str> {
+        self.as_service_error().map(|error| error.code.as_str())
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            S3Error::Service(error) => error.is_not_found(),
+            _ => self.status_code() == Some(404),
+        }
+    }
+
+    pub fn is_access_denied(&self) -> bool {
+        match self {
+            S3Error::Service(error) => error.is_access_denied(),
+            _ => self.status_code() == Some(403),
+        }
+    }
+
+    pub fn is_precondition_failed(&self) -> bool {
+        match self {
+            S3Error::Service(error) => error.is_precondition_failed(),
+            _ => self.status_code() == Some(412),
+        }
+    }
+
+    pub fn is_not_modified(&self) -> bool {
+        self.status_code() == Some(304)
+    }
 }
@@ -4,17 +4,27 @@
 pub use awscreds as creds;
 pub use awsregion as region;
 
-pub use bucket::Bucket;
+pub use body::PutBody;
 pub use bucket::Tag;
+pub use bucket::{Bucket, BucketBuilder};
 pub use bucket_ops::BucketConfiguration;
+pub use checksum::ChecksumAlgorithm;
+pub use object_ops::{CopyObjectOptions, CopySource, PutObjectOptions};
 pub use region::Region;
+pub use retry::RetryPolicy;
 
+pub mod body;
 pub mod bucket;
 pub mod bucket_ops;
+pub mod checksum;
 pub mod command;
 pub mod deserializer;
+pub mod multipart;
+pub mod object_ops;
+pub mod retry;
 pub mod serde_types;
 pub mod signing;
+pub mod transport;
 
 #[cfg(test)]
 pub mod bucket_tests;
@@ -26,3 +36,4 @@ pub mod utils;
 const LONG_DATETIME: &[time::format_description::FormatItem<'static>] =
     time::macros::format_description!("[year][month][day]T[hour][minute][second]Z");
 const EMPTY_PAYLOAD_SHA: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
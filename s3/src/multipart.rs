@@ -0,0 +1,238 @@
+//! Tuning of multipart uploads, see [`Bucket::put_object_stream`](crate::Bucket::put_object_stream),
+//! and the state needed to resume one, see
+//! [`Bucket::resume_multipart_upload`](crate::Bucket::resume_multipart_upload).
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bucket::{Part, CHUNK_SIZE};
+use crate::error::S3Error;
+
+/// The smallest part S3 accepts, except for the last one.
+pub const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+/// The largest part S3 accepts.
+pub const MAX_PART_SIZE: usize = 5 * 1024 * 1024 * 1024;
+/// The most parts a multipart upload can have.
+pub const MAX_PARTS: u32 = 10_000;
+
+/// Number of parts uploaded before the part size doubles.
+const PARTS_PER_SIZE: u32 = 1_000;
+
+/// How multipart uploads split and send their content.
+///
+/// Up to `concurrency` parts are uploaded at once while the next ones are
+/// read, so at most `concurrency` parts are held in memory.
+///
+/// The size of a streamed upload is not known up front, so to stay within
+/// the 10,000 part limit the part size doubles after every 1,000 parts. With
+/// the minimum part size of 5 MiB that still allows for objects close to
+/// the 5 TiB S3 allows.
+///
+/// # Example
+///
+/// ```
+/// use s3::multipart::MultipartConfig;
+///
+/// let config = MultipartConfig::default()
+///     .with_part_size(16 * 1024 * 1024)
+///     .with_concurrency(8);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultipartConfig {
+    /// Size of the first 1,000 parts, between [`MIN_PART_SIZE`] and [`MAX_PART_SIZE`].
+    pub part_size: usize,
+    /// Number of parts uploaded at the same time.
+    pub concurrency: usize,
+}
+
+impl Default for MultipartConfig {
+    fn default() -> Self {
+        MultipartConfig {
+            part_size: CHUNK_SIZE,
+            concurrency: 4,
+        }
+    }
+}
+
+impl MultipartConfig {
+    pub fn with_part_size(mut self, part_size: usize) -> Self {
+        self.part_size = part_size.clamp(MIN_PART_SIZE, MAX_PART_SIZE);
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// The size of part number `part_number` (1-based).
+    pub fn part_size_for(&self, part_number: u32) -> usize {
+        let doublings = part_number.saturating_sub(1) / PARTS_PER_SIZE;
+        self.part_size
+            .saturating_mul(1 << doublings.min(20))
+            .min(MAX_PART_SIZE)
+    }
+}
+
+/// Progress of a multipart upload that can outlive the process running it.
+///
+/// The state is created by
+/// [`Bucket::initiate_resumable_upload`](crate::Bucket::initiate_resumable_upload)
+/// and is best saved right away. Parts are reconciled with the server when
+/// resuming, so a state saved before all of its parts were recorded is still
+/// good to resume from.
+///
+/// # Example
+///
+/// ```no_run
+/// use s3::bucket::Bucket;
+/// use s3::creds::Credentials;
+/// use s3::multipart::MultipartUploadState;
+/// use anyhow::Result;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+///
+/// let bucket_name = "rust-s3-test";
+/// let region = "us-east-1".parse()?;
+/// let credentials = Credentials::default()?;
+/// let bucket = Bucket::new(bucket_name, region, credentials)?;
+///
+/// let mut state = match MultipartUploadState::load("upload.json").await {
+///     Ok(state) => state,
+///     Err(_) => {
+///         let state = bucket
+///             .initiate_resumable_upload("/large.file", "application/octet-stream")
+///             .await?;
+///         state.save("upload.json").await?;
+///         state
+///     }
+/// };
+///
+/// let mut file = tokio::fs::File::open("large.file").await?;
+/// bucket.resume_multipart_upload(&mut file, &mut state).await?;
+/// tokio::fs::remove_file("upload.json").await?;
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MultipartUploadState {
+    /// Upload ID returned when the upload was initiated.
+    pub upload_id: String,
+    /// Key of the object being uploaded.
+    pub key: String,
+    pub content_type: String,
+    /// Size of every part but the last one.
+    pub part_size: usize,
+    /// Parts known to be uploaded, with the ETags to complete the upload with.
+    pub parts: Vec<Part>,
+}
+
+impl MultipartUploadState {
+    pub fn new(
+        upload_id: impl Into<String>,
+        key: impl Into<String>,
+        content_type: impl Into<String>,
+        part_size: usize,
+    ) -> Self {
+        MultipartUploadState {
+            upload_id: upload_id.into(),
+            key: key.into(),
+            content_type: content_type.into(),
+            part_size: part_size.clamp(MIN_PART_SIZE, MAX_PART_SIZE),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Write the state as JSON to `path`.
+    ///
+    /// The file is replaced atomically, so a crash while saving leaves the
+    /// previous state behind.
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<(), S3Error> {
+        let path = path.as_ref();
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        tokio::fs::write(&tmp_path, serde_json::to_vec(self)?).await?;
+        tokio::fs::rename(&tmp_path, path).await?;
+        Ok(())
+    }
+
+    /// Read a state written by [`MultipartUploadState::save`].
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self, S3Error> {
+        let state = tokio::fs::read(path).await?;
+        Ok(serde_json::from_slice(&state)?)
+    }
+
+    /// The size of part `part_number` (1-based) of content `total_size` bytes long.
+    pub(crate) fn part_len(&self, part_number: u32, total_size: u64) -> u64 {
+        let start = (part_number as u64 - 1) * self.part_size as u64;
+        total_size.saturating_sub(start).min(self.part_size as u64)
+    }
+
+    /// The number of parts content `total_size` bytes long is split into.
+    pub(crate) fn part_count(&self, total_size: u64) -> u64 {
+        total_size.div_ceil(self.part_size as u64).max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultipartConfig, MultipartUploadState, MAX_PARTS, MAX_PART_SIZE, MIN_PART_SIZE};
+    use crate::bucket::Part;
+
+    #[test]
+    fn part_size_grows() {
+        let config = MultipartConfig::default().with_part_size(MIN_PART_SIZE);
+        assert_eq!(config.part_size_for(1), MIN_PART_SIZE);
+        assert_eq!(config.part_size_for(1000), MIN_PART_SIZE);
+        assert_eq!(config.part_size_for(1001), 2 * MIN_PART_SIZE);
+        assert_eq!(config.part_size_for(MAX_PARTS), 512 * MIN_PART_SIZE);
+
+        let capacity: u64 = (1..=MAX_PARTS)
+            .map(|part_number| config.part_size_for(part_number) as u64)
+            .sum();
+        assert!(capacity > 4 * 1024 * 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn part_size_is_bounded() {
+        let config = MultipartConfig::default().with_part_size(1);
+        assert_eq!(config.part_size, MIN_PART_SIZE);
+
+        let config = MultipartConfig::default().with_part_size(MAX_PART_SIZE);
+        assert_eq!(config.part_size_for(MAX_PARTS), MAX_PART_SIZE);
+        assert_eq!(
+            MultipartConfig::default().with_concurrency(0).concurrency,
+            1
+        );
+    }
+
+    #[test]
+    fn parts_of_state() {
+        let state = MultipartUploadState::new("upload", "test.file", "text/plain", 0);
+        assert_eq!(state.part_size, MIN_PART_SIZE);
+
+        let total_size = 2 * MIN_PART_SIZE as u64 + 1;
+        assert_eq!(state.part_count(total_size), 3);
+        assert_eq!(state.part_len(1, total_size), MIN_PART_SIZE as u64);
+        assert_eq!(state.part_len(3, total_size), 1);
+        assert_eq!(state.part_count(0), 1);
+        assert_eq!(state.part_len(1, 0), 0);
+    }
+
+    #[tokio::test]
+    async fn state_roundtrip() {
+        let mut state =
+            MultipartUploadState::new("upload", "test.file", "text/plain", MIN_PART_SIZE);
+        state.parts.push(Part::new(1, "\"etag-1\""));
+
+        let path = std::env::temp_dir().join(format!("s3-upload-{}.json", std::process::id()));
+        state.save(&path).await.unwrap();
+        let loaded = MultipartUploadState::load(&path).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+        assert_eq!(loaded, state);
+    }
+}
@@ -0,0 +1,920 @@
+use std::fmt;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use http::header::{
+    HeaderName, CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_ENCODING, CONTENT_LANGUAGE,
+    CONTENT_TYPE, EXPIRES, IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_UNMODIFIED_SINCE,
+};
+use http::HeaderMap;
+use time::OffsetDateTime;
+
+use crate::checksum::ChecksumAlgorithm;
+use crate::error::S3Error;
+use crate::serde_types::HeadObjectResult;
+use crate::signing;
+use crate::utils::http_date;
+
+/// [AWS Documentation](https://docs.aws.amazon.com/AmazonS3/latest/userguide/storage-class-intro.html)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageClass {
+    Standard,
+    ReducedRedundancy,
+    StandardIa,
+    OnezoneIa,
+    IntelligentTiering,
+    Glacier,
+    GlacierIr,
+    DeepArchive,
+    Custom(String),
+}
+
+impl fmt::Display for StorageClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageClass::Standard => write!(f, "STANDARD"),
+            StorageClass::ReducedRedundancy => write!(f, "REDUCED_REDUNDANCY"),
+            StorageClass::StandardIa => write!(f, "STANDARD_IA"),
+            StorageClass::OnezoneIa => write!(f, "ONEZONE_IA"),
+            StorageClass::IntelligentTiering => write!(f, "INTELLIGENT_TIERING"),
+            StorageClass::Glacier => write!(f, "GLACIER"),
+            StorageClass::GlacierIr => write!(f, "GLACIER_IR"),
+            StorageClass::DeepArchive => write!(f, "DEEP_ARCHIVE"),
+            StorageClass::Custom(storage_class) => write!(f, "{storage_class}"),
+        }
+    }
+}
+
+/// Server-side encryption of an object.
+///
+/// SSE-S3 and SSE-KMS only apply when writing, S3 decrypts those objects
+/// transparently. With SSE-C the same key has to be supplied to every read,
+/// upload part and copy of the object.
+///
+/// [AWS Documentation](https://docs.aws.amazon.com/AmazonS3/latest/userguide/serv-side-encryption.html)
+#[derive(Clone, PartialEq, Eq)]
+pub enum Encryption {
+    /// SSE-S3, `AES256` with keys managed by S3.
+    S3,
+    /// SSE-KMS, with the AWS managed `aws/s3` key unless `key_id` is set.
+    Kms { key_id: Option<String> },
+    /// SSE-C, with a 256-bit key managed by the client.
+    CustomerKey([u8; 32]),
+}
+
+// Keep customer keys out of logs
+impl fmt::Debug for Encryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encryption::S3 => write!(f, "S3"),
+            Encryption::Kms { key_id } => f.debug_struct("Kms").field("key_id", key_id).finish(),
+            Encryption::CustomerKey(_) => write!(f, "CustomerKey(..)"),
+        }
+    }
+}
+
+impl Encryption {
+    /// Add the headers requesting this encryption on a put, multipart
+    /// initiate or copy.
+    pub fn add_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        match self {
+            Encryption::S3 => {
+                headers.insert(
+                    HeaderName::from_static("x-amz-server-side-encryption"),
+                    "AES256".parse()?,
+                );
+            }
+            Encryption::Kms { key_id } => {
+                headers.insert(
+                    HeaderName::from_static("x-amz-server-side-encryption"),
+                    "aws:kms".parse()?,
+                );
+                if let Some(key_id) = key_id {
+                    headers.insert(
+                        HeaderName::from_static("x-amz-server-side-encryption-aws-kms-key-id"),
+                        key_id.parse()?,
+                    );
+                }
+            }
+            Encryption::CustomerKey(_) => self.add_customer_key_headers(headers)?,
+        }
+        Ok(())
+    }
+
+    /// Add the SSE-C headers a get, head or upload part needs, nothing for
+    /// the other kinds of encryption.
+    pub fn add_customer_key_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        self.customer_key_headers("x-amz-server-side-encryption-customer", headers)
+    }
+
+    /// Add the SSE-C headers to read the source of a copy.
+    pub fn add_copy_source_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        self.customer_key_headers("x-amz-copy-source-server-side-encryption-customer", headers)
+    }
+
+    fn customer_key_headers(&self, prefix: &str, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        if let Encryption::CustomerKey(key) = self {
+            let key_md5 = md5::compute(key);
+            headers.insert(
+                HeaderName::try_from(format!("{}-algorithm", prefix))?,
+                "AES256".parse()?,
+            );
+            headers.insert(
+                HeaderName::try_from(format!("{}-key", prefix))?,
+                general_purpose::STANDARD.encode(key).parse()?,
+            );
+            headers.insert(
+                HeaderName::try_from(format!("{}-key-md5", prefix))?,
+                general_purpose::STANDARD.encode(key_md5.as_ref()).parse()?,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// [AWS Documentation](https://docs.aws.amazon.com/AmazonS3/latest/userguide/acl-overview.html#canned-acl)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CannedObjectAcl {
+    Private,
+    PublicRead,
+    PublicReadWrite,
+    AuthenticatedRead,
+    AwsExecRead,
+    BucketOwnerRead,
+    BucketOwnerFullControl,
+    Custom(String),
+}
+
+impl fmt::Display for CannedObjectAcl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CannedObjectAcl::Private => write!(f, "private"),
+            CannedObjectAcl::PublicRead => write!(f, "public-read"),
+            CannedObjectAcl::PublicReadWrite => write!(f, "public-read-write"),
+            CannedObjectAcl::AuthenticatedRead => write!(f, "authenticated-read"),
+            CannedObjectAcl::AwsExecRead => write!(f, "aws-exec-read"),
+            CannedObjectAcl::BucketOwnerRead => write!(f, "bucket-owner-read"),
+            CannedObjectAcl::BucketOwnerFullControl => write!(f, "bucket-owner-full-control"),
+            CannedObjectAcl::Custom(acl) => write!(f, "{acl}"),
+        }
+    }
+}
+
+/// Settings of a put, streamed put or multipart upload, the counterpart of
+/// the fields read back in [`HeadObjectResult`](crate::serde_types::HeadObjectResult).
+///
+/// # Example
+///
+/// ```
+/// use s3::object_ops::{CannedObjectAcl, PutObjectOptions, StorageClass};
+///
+/// let options = PutObjectOptions::default()
+///     .with_content_type("text/html")
+///     .with_cache_control("max-age=3600")
+///     .with_metadata("origin", "upload")
+///     .with_storage_class(StorageClass::IntelligentTiering)
+///     .with_acl(CannedObjectAcl::PublicRead);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PutObjectOptions {
+    content_type: Option<String>,
+    cache_control: Option<String>,
+    content_disposition: Option<String>,
+    content_encoding: Option<String>,
+    content_language: Option<String>,
+    expires: Option<OffsetDateTime>,
+    metadata: Vec<(String, String)>,
+    storage_class: Option<StorageClass>,
+    acl: Option<CannedObjectAcl>,
+    tagging: Option<String>,
+    website_redirect_location: Option<String>,
+    encryption: Option<Encryption>,
+    checksum: Option<ChecksumAlgorithm>,
+}
+
+impl PutObjectOptions {
+    /// The content type, `application/octet-stream` unless set.
+    pub fn content_type(&self) -> &str {
+        self.content_type
+            .as_deref()
+            .unwrap_or("application/octet-stream")
+    }
+
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    pub fn with_cache_control(mut self, cache_control: impl Into<String>) -> Self {
+        self.cache_control = Some(cache_control.into());
+        self
+    }
+
+    pub fn with_content_disposition(mut self, content_disposition: impl Into<String>) -> Self {
+        self.content_disposition = Some(content_disposition.into());
+        self
+    }
+
+    pub fn with_content_encoding(mut self, content_encoding: impl Into<String>) -> Self {
+        self.content_encoding = Some(content_encoding.into());
+        self
+    }
+
+    pub fn with_content_language(mut self, content_language: impl Into<String>) -> Self {
+        self.content_language = Some(content_language.into());
+        self
+    }
+
+    /// The date after which the object is no longer cacheable.
+    pub fn with_expires(mut self, expires: OffsetDateTime) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// Add `x-amz-meta-{key}`.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_storage_class(mut self, storage_class: StorageClass) -> Self {
+        self.storage_class = Some(storage_class);
+        self
+    }
+
+    pub fn with_acl(mut self, acl: CannedObjectAcl) -> Self {
+        self.acl = Some(acl);
+        self
+    }
+
+    /// Tags as URL query parameters, e.g. `"team=storage&env=prod"`.
+    pub fn with_tagging(mut self, tagging: impl Into<String>) -> Self {
+        self.tagging = Some(tagging.into());
+        self
+    }
+
+    /// Redirect requests for the object when the bucket is served as a website.
+    pub fn with_website_redirect_location(mut self, location: impl Into<String>) -> Self {
+        self.website_redirect_location = Some(location.into());
+        self
+    }
+
+    pub fn with_encryption(mut self, encryption: Encryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Send an additional checksum of the content, or of every part of a
+    /// multipart upload, for S3 to verify and store along with the object.
+    pub fn with_checksum(mut self, algorithm: ChecksumAlgorithm) -> Self {
+        self.checksum = Some(algorithm);
+        self
+    }
+
+    pub fn checksum(&self) -> Option<ChecksumAlgorithm> {
+        self.checksum
+    }
+
+    /// The content headers and user metadata of an existing object, for a
+    /// multipart copy to carry over like a regular copy does.
+    pub(crate) fn from_head(head: &HeadObjectResult) -> Self {
+        let mut metadata: Vec<_> = head
+            .metadata
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        metadata.sort();
+        PutObjectOptions {
+            content_type: head.content_type.clone(),
+            cache_control: head.cache_control.clone(),
+            content_disposition: head.content_disposition.clone(),
+            content_encoding: head.content_encoding.clone(),
+            content_language: head.content_language.clone(),
+            metadata,
+            ..PutObjectOptions::default()
+        }
+    }
+
+    /// Add the headers every part of a multipart upload needs, the SSE-C key
+    /// if there is one.
+    pub fn add_part_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        if let Some(encryption) = &self.encryption {
+            encryption.add_customer_key_headers(headers)?;
+        }
+        Ok(())
+    }
+
+    /// Add the headers of all settings but the content type, which is sent
+    /// by the command itself.
+    pub fn add_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        if let Some(cache_control) = &self.cache_control {
+            headers.insert(CACHE_CONTROL, cache_control.parse()?);
+        }
+        if let Some(content_disposition) = &self.content_disposition {
+            headers.insert(CONTENT_DISPOSITION, content_disposition.parse()?);
+        }
+        if let Some(content_encoding) = &self.content_encoding {
+            headers.insert(CONTENT_ENCODING, content_encoding.parse()?);
+        }
+        if let Some(content_language) = &self.content_language {
+            headers.insert(CONTENT_LANGUAGE, content_language.parse()?);
+        }
+        if let Some(expires) = self.expires {
+            headers.insert(EXPIRES, http_date(expires)?.parse()?);
+        }
+        for (key, value) in &self.metadata {
+            headers.insert(
+                HeaderName::try_from(format!("x-amz-meta-{}", key))?,
+                value.parse()?,
+            );
+        }
+        if let Some(storage_class) = &self.storage_class {
+            headers.insert(
+                HeaderName::from_static("x-amz-storage-class"),
+                storage_class.to_string().parse()?,
+            );
+        }
+        if let Some(acl) = &self.acl {
+            headers.insert(
+                HeaderName::from_static("x-amz-acl"),
+                acl.to_string().parse()?,
+            );
+        }
+        if let Some(tagging) = &self.tagging {
+            headers.insert(HeaderName::from_static("x-amz-tagging"), tagging.parse()?);
+        }
+        if let Some(location) = &self.website_redirect_location {
+            headers.insert(
+                HeaderName::from_static("x-amz-website-redirect-location"),
+                location.parse()?,
+            );
+        }
+        if let Some(encryption) = &self.encryption {
+            encryption.add_headers(headers)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a copy keeps the metadata or tags of its source, or replaces
+/// them with the ones given along with the copy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Directive {
+    Copy,
+    Replace,
+}
+
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Directive::Copy => write!(f, "COPY"),
+            Directive::Replace => write!(f, "REPLACE"),
+        }
+    }
+}
+
+/// The object a copy reads from, in any bucket the credentials can read.
+///
+/// Buckets in other regions work as well, the copy is always sent to the
+/// region of the destination bucket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CopySource {
+    pub bucket: String,
+    pub key: String,
+    /// Copy a specific version instead of the current one.
+    pub version_id: Option<String>,
+}
+
+impl CopySource {
+    pub fn new(bucket: impl Into<String>, key: impl Into<String>) -> Self {
+        CopySource {
+            bucket: bucket.into(),
+            key: key.into(),
+            version_id: None,
+        }
+    }
+
+    pub fn with_version_id(mut self, version_id: impl Into<String>) -> Self {
+        self.version_id = Some(version_id.into());
+        self
+    }
+
+    /// The value of the `x-amz-copy-source` header.
+    pub fn header_value(&self) -> String {
+        let key = self.key.strip_prefix('/').unwrap_or(&self.key);
+        let mut source = signing::uri_encode(&format!("{}/{}", self.bucket, key), false);
+        if let Some(version_id) = &self.version_id {
+            source.push_str("?versionId=");
+            source.push_str(&signing::uri_encode(version_id, true));
+        }
+        source
+    }
+}
+
+/// Settings of a [`Bucket::copy_object_from`](crate::Bucket::copy_object_from).
+///
+/// Without a [`Directive::Replace`] the content type, metadata and tags of
+/// the source are kept, and the ones set here are ignored by S3.
+///
+/// # Example
+///
+/// ```
+/// use s3::object_ops::{CopyObjectOptions, Directive, StorageClass};
+///
+/// let options = CopyObjectOptions::default()
+///     .with_metadata_directive(Directive::Replace)
+///     .with_content_type("text/plain")
+///     .with_metadata("origin", "backup")
+///     .with_storage_class(StorageClass::StandardIa);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CopyObjectOptions {
+    metadata_directive: Option<Directive>,
+    tagging_directive: Option<Directive>,
+    content_type: Option<String>,
+    metadata: Vec<(String, String)>,
+    tagging: Option<String>,
+    storage_class: Option<StorageClass>,
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    if_modified_since: Option<OffsetDateTime>,
+    if_unmodified_since: Option<OffsetDateTime>,
+    encryption: Option<Encryption>,
+    source_encryption: Option<Encryption>,
+}
+
+impl CopyObjectOptions {
+    pub fn with_metadata_directive(mut self, directive: Directive) -> Self {
+        self.metadata_directive = Some(directive);
+        self
+    }
+
+    pub fn with_tagging_directive(mut self, directive: Directive) -> Self {
+        self.tagging_directive = Some(directive);
+        self
+    }
+
+    /// Content type of the copy, requires [`Directive::Replace`] for metadata.
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Add `x-amz-meta-{key}`, requires [`Directive::Replace`] for metadata.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    /// Tags of the copy as URL query parameters, e.g. `"team=storage&env=prod"`,
+    /// requires [`Directive::Replace`] for tagging.
+    pub fn with_tagging(mut self, tagging: impl Into<String>) -> Self {
+        self.tagging = Some(tagging.into());
+        self
+    }
+
+    pub fn with_storage_class(mut self, storage_class: StorageClass) -> Self {
+        self.storage_class = Some(storage_class);
+        self
+    }
+
+    /// Only copy if the ETag of the source matches.
+    pub fn with_if_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+
+    /// Only copy if the ETag of the source does not match.
+    pub fn with_if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_none_match = Some(etag.into());
+        self
+    }
+
+    /// Only copy if the source was modified after `date`.
+    pub fn with_if_modified_since(mut self, date: OffsetDateTime) -> Self {
+        self.if_modified_since = Some(date);
+        self
+    }
+
+    /// Only copy if the source was not modified after `date`.
+    pub fn with_if_unmodified_since(mut self, date: OffsetDateTime) -> Self {
+        self.if_unmodified_since = Some(date);
+        self
+    }
+
+    /// Encryption of the copy.
+    pub fn with_encryption(mut self, encryption: Encryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// The SSE-C key of the source, if it is encrypted with one.
+    pub fn with_source_encryption(mut self, encryption: Encryption) -> Self {
+        self.source_encryption = Some(encryption);
+        self
+    }
+
+    pub(crate) fn source_encryption(&self) -> Option<&Encryption> {
+        self.source_encryption.as_ref()
+    }
+
+    /// The settings of the object created by a multipart copy of the
+    /// object described by `head`.
+    pub(crate) fn multipart_options(&self, head: &HeadObjectResult) -> PutObjectOptions {
+        let mut options = match self.metadata_directive {
+            Some(Directive::Replace) => PutObjectOptions {
+                content_type: self.content_type.clone(),
+                metadata: self.metadata.clone(),
+                ..PutObjectOptions::default()
+            },
+            _ => PutObjectOptions::from_head(head),
+        };
+        if let Some(Directive::Replace) = self.tagging_directive {
+            options.tagging = self.tagging.clone();
+        }
+        options.storage_class = self.storage_class.clone();
+        options.encryption = self.encryption.clone();
+        options
+    }
+
+    /// Add the headers every part of a multipart copy needs, the source
+    /// preconditions and the SSE-C keys of the source and the copy.
+    pub fn add_part_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        self.add_source_headers(headers)?;
+        if let Some(encryption) = &self.encryption {
+            encryption.add_customer_key_headers(headers)?;
+        }
+        Ok(())
+    }
+
+    pub fn add_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        if let Some(directive) = self.metadata_directive {
+            headers.insert(
+                HeaderName::from_static("x-amz-metadata-directive"),
+                directive.to_string().parse()?,
+            );
+        }
+        if let Some(directive) = self.tagging_directive {
+            headers.insert(
+                HeaderName::from_static("x-amz-tagging-directive"),
+                directive.to_string().parse()?,
+            );
+        }
+        if let Some(content_type) = &self.content_type {
+            headers.insert(CONTENT_TYPE, content_type.parse()?);
+        }
+        for (key, value) in &self.metadata {
+            headers.insert(
+                HeaderName::try_from(format!("x-amz-meta-{}", key))?,
+                value.parse()?,
+            );
+        }
+        if let Some(tagging) = &self.tagging {
+            headers.insert(HeaderName::from_static("x-amz-tagging"), tagging.parse()?);
+        }
+        if let Some(storage_class) = &self.storage_class {
+            headers.insert(
+                HeaderName::from_static("x-amz-storage-class"),
+                storage_class.to_string().parse()?,
+            );
+        }
+        if let Some(encryption) = &self.encryption {
+            encryption.add_headers(headers)?;
+        }
+        self.add_source_headers(headers)
+    }
+
+    fn add_source_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        if let Some(etag) = &self.if_match {
+            headers.insert(
+                HeaderName::from_static("x-amz-copy-source-if-match"),
+                etag.parse()?,
+            );
+        }
+        if let Some(etag) = &self.if_none_match {
+            headers.insert(
+                HeaderName::from_static("x-amz-copy-source-if-none-match"),
+                etag.parse()?,
+            );
+        }
+        if let Some(date) = self.if_modified_since {
+            headers.insert(
+                HeaderName::from_static("x-amz-copy-source-if-modified-since"),
+                http_date(date)?.parse()?,
+            );
+        }
+        if let Some(date) = self.if_unmodified_since {
+            headers.insert(
+                HeaderName::from_static("x-amz-copy-source-if-unmodified-since"),
+                http_date(date)?.parse()?,
+            );
+        }
+        if let Some(encryption) = &self.source_encryption {
+            encryption.add_copy_source_headers(headers)?;
+        }
+        Ok(())
+    }
+}
+
+/// Preconditions of a get, head or put, sent as the `If-*` headers.
+///
+/// # Example
+///
+/// ```
+/// use s3::object_ops::Conditions;
+///
+/// // Revalidate a cached copy
+/// let revalidate = Conditions::default().with_if_none_match("\"9b2cf535f27731c974343645a3985328\"");
+///
+/// // Put only if nothing exists at the key yet
+/// let create = Conditions::create_only();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Conditions {
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    if_modified_since: Option<OffsetDateTime>,
+    if_unmodified_since: Option<OffsetDateTime>,
+}
+
+impl Conditions {
+    /// `If-None-Match: *`, a put that fails if the object already exists.
+    pub fn create_only() -> Self {
+        Conditions::default().with_if_none_match("*")
+    }
+
+    /// Only proceed if the ETag of the object matches.
+    pub fn with_if_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+
+    /// Only proceed if the ETag of the object does not match, `"*"` matches any object.
+    pub fn with_if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_none_match = Some(etag.into());
+        self
+    }
+
+    /// Only proceed if the object was modified after `date`.
+    pub fn with_if_modified_since(mut self, date: OffsetDateTime) -> Self {
+        self.if_modified_since = Some(date);
+        self
+    }
+
+    /// Only proceed if the object was not modified after `date`.
+    pub fn with_if_unmodified_since(mut self, date: OffsetDateTime) -> Self {
+        self.if_unmodified_since = Some(date);
+        self
+    }
+
+    pub fn add_headers(&self, headers: &mut HeaderMap) -> Result<(), S3Error> {
+        if let Some(etag) = &self.if_match {
+            headers.insert(IF_MATCH, etag.parse()?);
+        }
+        if let Some(etag) = &self.if_none_match {
+            headers.insert(IF_NONE_MATCH, etag.parse()?);
+        }
+        if let Some(date) = self.if_modified_since {
+            headers.insert(IF_MODIFIED_SINCE, http_date(date)?.parse()?);
+        }
+        if let Some(date) = self.if_unmodified_since {
+            headers.insert(IF_UNMODIFIED_SINCE, http_date(date)?.parse()?);
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of a request sent with [`Conditions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Conditional<T> {
+    /// The preconditions held and the request went through.
+    Met(T),
+    /// HTTP 304, the object matches `If-None-Match` or was not modified
+    /// since `If-Modified-Since`.
+    NotModified,
+    /// HTTP 412, `If-Match` or `If-Unmodified-Since` did not hold, or a
+    /// create-only put found an existing object.
+    PreconditionFailed,
+}
+
+impl<T> Conditional<T> {
+    pub fn is_met(&self) -> bool {
+        matches!(self, Conditional::Met(_))
+    }
+
+    /// The result of the request, if the preconditions held.
+    pub fn met(self) -> Option<T> {
+        match self {
+            Conditional::Met(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_status(status: u16, value: T) -> Self {
+        match status {
+            304 => Conditional::NotModified,
+            412 => Conditional::PreconditionFailed,
+            _ => Conditional::Met(value),
+        }
+    }
+
+    /// With `fail-on-err`, 304 and 412 responses arrive as errors.
+    pub(crate) fn from_error(error: S3Error) -> Result<Self, S3Error> {
+        if error.is_not_modified() {
+            Ok(Conditional::NotModified)
+        } else if error.is_precondition_failed() {
+            Ok(Conditional::PreconditionFailed)
+        } else {
+            Err(error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+    use time::macros::datetime;
+
+    use super::{
+        CannedObjectAcl, Conditional, Conditions, CopyObjectOptions, CopySource, Directive,
+        Encryption, PutObjectOptions, StorageClass,
+    };
+    use crate::error::{S3Error, ServiceError};
+
+    #[test]
+    fn copy_source() {
+        assert_eq!(
+            CopySource::new("other-bucket", "/dir/my file.txt").header_value(),
+            "other-bucket/dir/my%20file.txt"
+        );
+        assert_eq!(
+            CopySource::new("other-bucket", "file.txt")
+                .with_version_id("3/L4kqtJl40Nr8X8gdRQBpUMLUo")
+                .header_value(),
+            "other-bucket/file.txt?versionId=3%2FL4kqtJl40Nr8X8gdRQBpUMLUo"
+        );
+    }
+
+    #[test]
+    fn encryption_headers() {
+        let mut headers = HeaderMap::new();
+        Encryption::S3.add_headers(&mut headers).unwrap();
+        assert_eq!(headers["x-amz-server-side-encryption"], "AES256");
+
+        let kms = Encryption::Kms {
+            key_id: Some("arn:aws:kms:us-east-1:111122223333:key/example".to_string()),
+        };
+        let mut headers = HeaderMap::new();
+        kms.add_headers(&mut headers).unwrap();
+        assert_eq!(headers["x-amz-server-side-encryption"], "aws:kms");
+        assert_eq!(
+            headers["x-amz-server-side-encryption-aws-kms-key-id"],
+            "arn:aws:kms:us-east-1:111122223333:key/example"
+        );
+        // Reads need no headers for keys managed by AWS
+        let mut headers = HeaderMap::new();
+        kms.add_customer_key_headers(&mut headers).unwrap();
+        assert!(headers.is_empty());
+
+        let customer_key = Encryption::CustomerKey([7; 32]);
+        let mut headers = HeaderMap::new();
+        customer_key.add_customer_key_headers(&mut headers).unwrap();
+        assert_eq!(
+            headers["x-amz-server-side-encryption-customer-algorithm"],
+            "AES256"
+        );
+        assert_eq!(
+            headers["x-amz-server-side-encryption-customer-key"],
+            "BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc="
+        );
+        assert_eq!(
+            headers["x-amz-server-side-encryption-customer-key-md5"],
+            "y4HAEFCYWuvAXWFTtA1Qpg=="
+        );
+        assert_eq!(format!("{:?}", customer_key), "CustomerKey(..)");
+
+        let mut headers = HeaderMap::new();
+        CopyObjectOptions::default()
+            .with_encryption(Encryption::S3)
+            .with_source_encryption(customer_key)
+            .add_headers(&mut headers)
+            .unwrap();
+        assert_eq!(headers["x-amz-server-side-encryption"], "AES256");
+        assert_eq!(
+            headers["x-amz-copy-source-server-side-encryption-customer-key-md5"],
+            "y4HAEFCYWuvAXWFTtA1Qpg=="
+        );
+    }
+
+    #[test]
+    fn put_headers() {
+        let options = PutObjectOptions::default()
+            .with_content_type("text/html")
+            .with_cache_control("max-age=3600")
+            .with_content_disposition("attachment; filename=\"index.html\"")
+            .with_expires(datetime!(2024-01-02 3:04:05 UTC))
+            .with_metadata("origin", "upload")
+            .with_storage_class(StorageClass::IntelligentTiering)
+            .with_acl(CannedObjectAcl::BucketOwnerFullControl)
+            .with_tagging("team=storage")
+            .with_website_redirect_location("/other.html");
+        assert_eq!(options.content_type(), "text/html");
+        assert_eq!(
+            PutObjectOptions::default().content_type(),
+            "application/octet-stream"
+        );
+
+        let mut headers = HeaderMap::new();
+        options.add_headers(&mut headers).unwrap();
+        assert_eq!(headers["cache-control"], "max-age=3600");
+        assert_eq!(
+            headers["content-disposition"],
+            "attachment; filename=\"index.html\""
+        );
+        assert_eq!(headers["expires"], "Tue, 02 Jan 2024 03:04:05 GMT");
+        assert_eq!(headers["x-amz-meta-origin"], "upload");
+        assert_eq!(headers["x-amz-storage-class"], "INTELLIGENT_TIERING");
+        assert_eq!(headers["x-amz-acl"], "bucket-owner-full-control");
+        assert_eq!(headers["x-amz-tagging"], "team=storage");
+        assert_eq!(headers["x-amz-website-redirect-location"], "/other.html");
+        assert!(!headers.contains_key("content-type"));
+        assert!(!headers.contains_key("content-encoding"));
+    }
+
+    #[test]
+    fn copy_headers() {
+        let options = CopyObjectOptions::default()
+            .with_metadata_directive(Directive::Replace)
+            .with_tagging_directive(Directive::Copy)
+            .with_content_type("text/plain")
+            .with_metadata("origin", "backup")
+            .with_storage_class(StorageClass::StandardIa)
+            .with_if_none_match("\"etag\"")
+            .with_if_unmodified_since(datetime!(2024-01-02 3:04:05 +1));
+
+        let mut headers = HeaderMap::new();
+        options.add_headers(&mut headers).unwrap();
+        assert_eq!(headers["x-amz-metadata-directive"], "REPLACE");
+        assert_eq!(headers["x-amz-tagging-directive"], "COPY");
+        assert_eq!(headers["content-type"], "text/plain");
+        assert_eq!(headers["x-amz-meta-origin"], "backup");
+        assert_eq!(headers["x-amz-storage-class"], "STANDARD_IA");
+        assert_eq!(headers["x-amz-copy-source-if-none-match"], "\"etag\"");
+        assert_eq!(
+            headers["x-amz-copy-source-if-unmodified-since"],
+            "Tue, 02 Jan 2024 02:04:05 GMT"
+        );
+        assert!(!headers.contains_key("x-amz-copy-source-if-match"));
+    }
+
+    #[test]
+    fn condition_headers() {
+        let mut headers = HeaderMap::new();
+        Conditions::default()
+            .with_if_match("\"etag\"")
+            .with_if_modified_since(datetime!(2024-01-02 3:04:05 UTC))
+            .add_headers(&mut headers)
+            .unwrap();
+        assert_eq!(headers["if-match"], "\"etag\"");
+        assert_eq!(
+            headers["if-modified-since"],
+            "Tue, 02 Jan 2024 03:04:05 GMT"
+        );
+        assert!(!headers.contains_key("if-none-match"));
+
+        let mut headers = HeaderMap::new();
+        Conditions::create_only().add_headers(&mut headers).unwrap();
+        assert_eq!(headers["if-none-match"], "*");
+    }
+
+    #[test]
+    fn conditional_outcomes() {
+        assert_eq!(Conditional::from_status(200, ()), Conditional::Met(()));
+        assert_eq!(Conditional::from_status(304, ()), Conditional::NotModified);
+        assert_eq!(
+            Conditional::from_status(412, ()),
+            Conditional::PreconditionFailed
+        );
+
+        let error = |status, code: &str| {
+            S3Error::Service(Box::new(ServiceError {
+                status,
+                code: code.to_string(),
+                message: String::new(),
+                request_id: None,
+                host_id: None,
+                resource: None,
+            }))
+        };
+        assert_eq!(
+            Conditional::<()>::from_error(error(304, "NotModified")).unwrap(),
+            Conditional::NotModified
+        );
+        assert_eq!(
+            Conditional::<()>::from_error(error(412, "PreconditionFailed")).unwrap(),
+            Conditional::PreconditionFailed
+        );
+        assert!(Conditional::<()>::from_error(error(404, "NoSuchKey")).is_err());
+    }
+}
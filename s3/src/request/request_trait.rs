@@ -2,16 +2,17 @@ use base64::engine::general_purpose;
 use base64::Engine;
 use hmac::Mac;
 use http_body_util::BodyStream;
-use hyper::body::Incoming;
 use std::collections::HashMap;
 use time::format_description::well_known::Rfc2822;
 use time::OffsetDateTime;
 use url::Url;
 
 use crate::bucket::Bucket;
+use crate::checksum::ChecksumAlgorithm;
 use crate::command::Command;
 use crate::error::S3Error;
 use crate::signing;
+use crate::transport::{self, Body};
 use crate::LONG_DATETIME;
 use bytes::Bytes;
 use http::header::{
@@ -86,7 +87,7 @@ impl fmt::Display for ResponseData {
     }
 }
 
-pub type DataStream = BodyStream<Incoming>;
+pub type DataStream = BodyStream<Body>;
 
 pub struct ResponseDataStream {
     pub body_stream: DataStream,
@@ -113,6 +114,14 @@ pub trait Request: Send + Sync {
         &self,
         writer: &mut T,
     ) -> impl std::future::Future<Output = Result<u16, S3Error>> + Send;
+    /// Like [`response_data_to_writer`](Self::response_data_to_writer), but
+    /// verify the content against the `algorithm` checksum S3 returned, if
+    /// it returned one for the whole object.
+    fn response_data_to_writer_with_checksum<T: tokio::io::AsyncWrite + Send + Unpin>(
+        &self,
+        writer: &mut T,
+        algorithm: ChecksumAlgorithm,
+    ) -> impl std::future::Future<Output = Result<u16, S3Error>> + Send;
     fn response_data_to_stream(
         &self,
     ) -> impl std::future::Future<Output = Result<ResponseDataStream, S3Error>> + Send;
@@ -121,7 +130,7 @@ pub trait Request: Send + Sync {
     ) -> impl std::future::Future<Output = Result<(Self::HeaderMap, u16), S3Error>> + Send;
     fn datetime(&self) -> OffsetDateTime;
     fn bucket(&self) -> Bucket;
-    fn command(&self) -> Command;
+    fn command(&self) -> Command<'_>;
     fn path(&self) -> String;
 
     fn signing_key(&self) -> Result<Vec<u8>, S3Error> {
@@ -136,17 +145,49 @@ pub trait Request: Send + Sync {
         )
     }
 
-    fn request_body(&self) -> Vec<u8> {
-        match self.command() {
-            Command::PutObject { content, .. } => Vec::from(content),
+    /// The body to send along with the signed `headers`.
+    fn request_body(&self, headers: &HeaderMap) -> Result<Body, S3Error> {
+        let body = match self.command() {
+            Command::PutObject { content, .. } => {
+                let signer = if content.is_aws_chunked() {
+                    self.chunk_signer(headers)?
+                } else {
+                    None
+                };
+                return content.to_body(signer);
+            }
             Command::PutObjectTagging { tags } => Vec::from(tags),
             Command::UploadPart { content, .. } => Vec::from(content),
             Command::CompleteMultipartUpload { data, .. } => data.to_string().as_bytes().to_vec(),
+            Command::PutBucketVersioning { configuration } => {
+                configuration.to_string().as_bytes().to_vec()
+            }
+            Command::DeleteObjects { data } => data.to_string().as_bytes().to_vec(),
             Command::CreateBucket { config } => config
                 .location_constraint_payload()
                 .map(Vec::from)
                 .unwrap_or_default(),
             _ => vec![],
+        };
+        Ok(transport::full(body))
+    }
+
+    /// A signer for aws-chunked content, seeded with the signature in the
+    /// `Authorization` header, if there is one.
+    fn chunk_signer(&self, headers: &HeaderMap) -> Result<Option<signing::ChunkSigner>, S3Error> {
+        let seed_signature = headers
+            .get(AUTHORIZATION)
+            .map(|authorization| authorization.to_str())
+            .transpose()?
+            .and_then(|authorization| authorization.rsplit_once("Signature="));
+        match seed_signature {
+            Some((_, seed_signature)) => Ok(Some(signing::ChunkSigner::new(
+                self.signing_key()?,
+                &self.datetime(),
+                &self.bucket().region(),
+                seed_signature,
+            )?)),
+            None => Ok(None),
         }
     }
 
@@ -174,7 +215,8 @@ pub trait Request: Send + Sync {
             Command::PresignGet {
                 expiry_secs,
                 custom_queries,
-            } => (expiry_secs, None, custom_queries),
+                custom_headers,
+            } => (expiry_secs, custom_headers, custom_queries),
             Command::PresignPut {
                 expiry_secs,
                 custom_headers,
@@ -216,7 +258,8 @@ pub trait Request: Send + Sync {
             Command::PresignGet {
                 expiry_secs,
                 custom_queries,
-            } => (expiry_secs, None, custom_queries),
+                custom_headers,
+            } => (expiry_secs, custom_headers, custom_queries),
             Command::PresignPut {
                 expiry_secs,
                 custom_headers,
@@ -288,18 +331,29 @@ pub trait Request: Send + Sync {
             Command::InitiateMultipartUpload { .. } | Command::ListMultipartUploads { .. } => {
                 url_str.push_str("?uploads")
             }
+            Command::ListObjectVersions { .. } => url_str.push_str("?versions"),
+            Command::DeleteObjects { .. } => url_str.push_str("?delete"),
+            Command::GetBucketVersioning | Command::PutBucketVersioning { .. } => {
+                url_str.push_str("?versioning")
+            }
             Command::AbortMultipartUpload { upload_id } => {
                 write!(url_str, "?uploadId={}", upload_id).expect("Could not write to url_str");
             }
             Command::CompleteMultipartUpload { upload_id, .. } => {
                 write!(url_str, "?uploadId={}", upload_id).expect("Could not write to url_str");
             }
+            Command::ListParts { upload_id, .. } => {
+                write!(url_str, "?uploadId={}", upload_id).expect("Could not write to url_str");
+            }
             Command::GetObjectTorrent => url_str.push_str("?torrent"),
             Command::PutObject { multipart, .. } => {
                 if let Some(multipart) = multipart {
                     url_str.push_str(&multipart.query_string())
                 }
             }
+            Command::UploadPartCopy { multipart, .. } => {
+                url_str.push_str(&multipart.query_string())
+            }
             _ => {}
         }
 
@@ -352,7 +406,35 @@ pub trait Request: Send + Sync {
             }
         }
 
+        if let Command::ListObjectVersions {
+            prefix,
+            delimiter,
+            key_marker,
+            version_id_marker,
+            max_keys,
+        } = self.command().clone()
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            delimiter.map(|d| query_pairs.append_pair("delimiter", &d));
+
+            query_pairs.append_pair("prefix", &prefix);
+            if let Some(key_marker) = key_marker {
+                query_pairs.append_pair("key-marker", &key_marker);
+            }
+            if let Some(version_id_marker) = version_id_marker {
+                query_pairs.append_pair("version-id-marker", &version_id_marker);
+            }
+            if let Some(max_keys) = max_keys {
+                query_pairs.append_pair("max-keys", &max_keys.to_string());
+            }
+        }
+
         match self.command() {
+            Command::GetObjectVersion { version_id }
+            | Command::HeadObjectVersion { version_id }
+            | Command::DeleteObjectVersion { version_id } => {
+                url.query_pairs_mut().append_pair("versionId", version_id);
+            }
             Command::ListMultipartUploads {
                 prefix,
                 delimiter,
@@ -371,6 +453,19 @@ pub trait Request: Send + Sync {
                     query_pairs.append_pair("max-uploads", max_uploads.to_string().as_str());
                 }
             }
+            Command::ListParts {
+                part_number_marker,
+                max_parts,
+                ..
+            } => {
+                let mut query_pairs = url.query_pairs_mut();
+                if let Some(part_number_marker) = part_number_marker {
+                    query_pairs.append_pair("part-number-marker", &part_number_marker.to_string());
+                }
+                if let Some(max_parts) = max_parts {
+                    query_pairs.append_pair("max-parts", &max_parts.to_string());
+                }
+            }
             Command::PutObjectTagging { .. }
             | Command::GetObjectTagging
             | Command::DeleteObjectTagging => {
@@ -425,12 +520,29 @@ pub trait Request: Send + Sync {
         headers.insert(HOST, host_header.parse()?);
 
         match self.command() {
-            Command::CopyObject { from } => {
+            Command::CopyObject { from, options } => {
                 headers.insert(HeaderName::from_static("x-amz-copy-source"), from.parse()?);
+                options.add_headers(&mut headers)?;
+            }
+            Command::UploadPartCopy {
+                from,
+                start,
+                end,
+                options,
+                ..
+            } => {
+                headers.insert(HeaderName::from_static("x-amz-copy-source"), from.parse()?);
+                headers.insert(
+                    HeaderName::from_static("x-amz-copy-source-range"),
+                    format!("bytes={}-{}", start, end).parse()?,
+                );
+                options.add_part_headers(&mut headers)?;
             }
             Command::ListObjects { .. } => {}
             Command::ListObjectsV2 { .. } => {}
             Command::GetObject => {}
+            Command::GetObjectWithOptions { .. } => {}
+            Command::GetObjectVersion { .. } => {}
             Command::GetObjectTagging => {}
             Command::GetBucketLocation => {}
             _ => {
@@ -466,15 +578,30 @@ pub trait Request: Send + Sync {
             let digest = md5::compute(tags);
             let hash = general_purpose::STANDARD.encode(digest.as_ref());
             headers.insert(HeaderName::from_static("content-md5"), hash.parse()?);
-        } else if let Command::PutObject { content, .. } = self.command() {
-            let digest = md5::compute(content);
+        } else if let Command::PutBucketVersioning { configuration } = self.command() {
+            let digest = md5::compute(configuration.to_string());
             let hash = general_purpose::STANDARD.encode(digest.as_ref());
             headers.insert(HeaderName::from_static("content-md5"), hash.parse()?);
+        } else if let Command::DeleteObjects { data } = self.command() {
+            let digest = md5::compute(data.to_string());
+            let hash = general_purpose::STANDARD.encode(digest.as_ref());
+            headers.insert(HeaderName::from_static("content-md5"), hash.parse()?);
+        } else if let Command::PutObject { content, .. } = self.command() {
+            // Streamed content is not known up front
+            if let Some(content) = content.as_bytes() {
+                let digest = md5::compute(content);
+                let hash = general_purpose::STANDARD.encode(digest.as_ref());
+                headers.insert(HeaderName::from_static("content-md5"), hash.parse()?);
+            }
+            content.add_headers(&mut headers)?;
         } else if let Command::UploadPart { content, .. } = self.command() {
             let digest = md5::compute(content);
             let hash = general_purpose::STANDARD.encode(digest.as_ref());
             headers.insert(HeaderName::from_static("content-md5"), hash.parse()?);
-        } else if let Command::GetObject {} = self.command() {
+        } else if let Command::GetObject
+        | Command::GetObjectWithOptions { .. }
+        | Command::GetObjectVersion { .. } = self.command()
+        {
             headers.insert(ACCEPT, "application/octet-stream".to_string().parse()?);
         // headers.insert(header::ACCEPT_CHARSET, HeaderValue::from_str("UTF-8")?);
         } else if let Command::GetObjectRange { start, end } = self.command() {
@@ -491,6 +618,91 @@ pub trait Request: Send + Sync {
             config.add_headers(&mut headers)?;
         }
 
+        if let Command::GetObjectWithOptions {
+            conditions: Some(conditions),
+            ..
+        }
+        | Command::HeadObjectWithOptions {
+            conditions: Some(conditions),
+            ..
+        }
+        | Command::PutObject {
+            conditions: Some(conditions),
+            ..
+        } = self.command()
+        {
+            conditions.add_headers(&mut headers)?;
+        }
+        if let Command::GetObjectWithOptions {
+            encryption: Some(encryption),
+            ..
+        }
+        | Command::HeadObjectWithOptions {
+            encryption: Some(encryption),
+            ..
+        } = self.command()
+        {
+            encryption.add_customer_key_headers(&mut headers)?;
+        }
+        if let Command::PutObject {
+            options: Some(options),
+            multipart: Some(_),
+            ..
+        } = self.command()
+        {
+            options.add_part_headers(&mut headers)?;
+        } else if let Command::PutObject {
+            options: Some(options),
+            ..
+        }
+        | Command::InitiateMultipartUpload {
+            options: Some(options),
+            ..
+        } = self.command()
+        {
+            options.add_headers(&mut headers)?;
+        }
+
+        match self.command() {
+            Command::GetObjectWithOptions {
+                checksum_mode: true,
+                ..
+            } => {
+                headers.insert(
+                    HeaderName::from_static("x-amz-checksum-mode"),
+                    "ENABLED".parse()?,
+                );
+            }
+            Command::PutObject {
+                multipart: Some(multipart),
+                ..
+            } => multipart.add_headers(&mut headers)?,
+            Command::PutObject {
+                content,
+                options: Some(options),
+                ..
+            } => {
+                if let (Some(algorithm), Some(content)) = (options.checksum(), content.as_bytes()) {
+                    headers.insert(
+                        HeaderName::from_static(algorithm.header_name()),
+                        algorithm.checksum(content).parse()?,
+                    );
+                }
+            }
+            Command::InitiateMultipartUpload {
+                options: Some(options),
+                ..
+            } => {
+                if let Some(algorithm) = options.checksum() {
+                    headers.insert(
+                        HeaderName::from_static("x-amz-checksum-algorithm"),
+                        algorithm.to_string().parse()?,
+                    );
+                }
+            }
+            _ => {}
+        }
+
         // This must be last, as it signs the other headers, omitted if no secret key is provided
         if self.bucket().secret_key()?.is_some() {
             let authorization = self.authorization(&headers)?;
@@ -5,17 +5,19 @@ use bytes::Bytes;
 use futures::TryStreamExt;
 use http_body_util::BodyExt;
 use http_body_util::BodyStream;
-use http_body_util::Full;
-use hyper::body::Incoming;
 use std::collections::HashMap;
 use time::OffsetDateTime;
 use tokio::io;
 
 use super::request_trait::{Request, ResponseData};
 use crate::bucket::Bucket;
+use crate::checksum::ChecksumAlgorithm;
 use crate::command::Command;
 use crate::command::HttpMethod;
 use crate::error::S3Error;
+use crate::retry::RetryPolicy;
+use crate::transport::Body;
+use crate::utils::error_from_body;
 
 pub use crate::request::tokio_backend::HyperRequest as RequestImpl;
 pub use tokio::io::AsyncWriteExt;
@@ -35,64 +37,67 @@ pub struct HyperRequest<'a> {
 }
 
 impl<'a> Request for HyperRequest<'a> {
-    type Response = http::Response<Incoming>;
+    type Response = http::Response<Body>;
     type HeaderMap = http::header::HeaderMap;
 
-    async fn response(&self) -> Result<http::Response<Incoming>, S3Error> {
-        // Build headers
-        let headers = match self.headers() {
-            Ok(headers) => headers,
-            Err(e) => return Err(e),
+    async fn response(&self) -> Result<http::Response<Body>, S3Error> {
+        // A streamed body can only be sent once.
+        let replayable = match &self.command {
+            Command::PutObject { content, .. } => content.is_replayable(),
+            _ => true,
         };
-        let client = self.bucket.http_client();
-
-        let method = match self.command.http_verb() {
-            HttpMethod::Delete => http::Method::DELETE,
-            HttpMethod::Get => http::Method::GET,
-            HttpMethod::Post => http::Method::POST,
-            HttpMethod::Put => http::Method::PUT,
-            HttpMethod::Head => http::Method::HEAD,
+        let policy = if replayable {
+            self.bucket.retry_policy().clone()
+        } else {
+            RetryPolicy::none()
         };
-
-        let request: http::Request<Full<Bytes>> = {
-            let mut request = http::Request::builder()
-                .method(method)
-                .uri(self.url()?.as_str());
-
-            for (header, value) in headers.iter() {
-                request = request.header(header, value);
+        let mut attempt = 1;
+
+        loop {
+            // Every retry is signed again, with a fresh timestamp.
+            let request = HyperRequest {
+                datetime: if attempt == 1 {
+                    self.datetime
+                } else {
+                    OffsetDateTime::now_utc()
+                },
+                command: self.command.clone(),
+                ..*self
+            };
+
+            match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    if policy.is_retryable_status(status) && policy.has_attempts_left(attempt) {
+                        #[cfg(feature = "tracing")]
+                        event!(Level::DEBUG, status_code = status, attempt, "retrying");
+                    } else if cfg!(feature = "fail-on-err") {
+                        let data = response.collect().await?;
+                        let text = String::from_utf8(data.to_bytes().to_vec())?;
+                        let error = error_from_body(status, text);
+                        let retryable_code = error
+                            .code()
+                            .is_some_and(|code| policy.is_retryable_code(code));
+                        if !(retryable_code && policy.has_attempts_left(attempt)) {
+                            return Err(error);
+                        }
+                    } else {
+                        return Ok(response);
+                    }
+                }
+                Err(error) => {
+                    if !(policy.is_retryable_error(&error) && policy.has_attempts_left(attempt)) {
+                        return Err(error);
+                    }
+                    #[cfg(feature = "tracing")]
+                    event!(Level::DEBUG, error = %error, attempt, "retrying");
+                }
             }
-            request.body(Full::from(Bytes::from(self.request_body())))?
-        };
-        #[cfg(feature = "tracing")]
-        let span = span!(
-            Level::DEBUG,
-            "rust-s3-async",
-            bucket = self.bucket.name(),
-            command = self.command.to_string(),
-            path = self.path,
-            second = self.datetime.second(),
-            minute = self.datetime.minute(),
-            hour = self.datetime.hour(),
-            day = self.datetime.day(),
-            month = self.datetime.month() as u8,
-            year = self.datetime.year()
-        );
-        #[cfg(feature = "tracing")]
-        let _enter = span.enter();
-        let response = client.request(request).await?;
-
-        #[cfg(feature = "tracing")]
-        event!(Level::DEBUG, status_code = response.status().as_u16(),);
 
-        if cfg!(feature = "fail-on-err") && !response.status().is_success() {
-            let status = response.status().as_u16();
-            let data = response.collect().await?;
-            let text = String::from_utf8(data.to_bytes().to_vec())?;
-            return Err(S3Error::HttpFailWithBody(status, text));
+            tokio::time::sleep(policy.backoff(attempt)).await;
+            attempt += 1;
         }
-
-        Ok(response)
     }
 
     async fn response_data(&self, etag: bool) -> Result<ResponseData, S3Error> {
@@ -130,23 +135,41 @@ impl<'a> Request for HyperRequest<'a> {
         let response = self.response().await?;
         let status_code = response.status();
 
-        let body: Incoming = response.into_body();
-        let stream: BodyStream<Incoming> = BodyStream::new(body);
-
-        let stream_of_bytes = stream
-            .try_filter_map(
-                |frame: hyper::body::Frame<Bytes>| async move { Ok(frame.into_data().ok()) },
-            )
-            .map_err(|err| io::Error::new(io::ErrorKind::Other, err));
-
-        let async_read = tokio_util::io::StreamReader::new(stream_of_bytes);
-        let buffer_size = 512 * 1024; //512 KiB
-        let async_read = tokio::io::BufReader::with_capacity(buffer_size, async_read);
+        copy_to_writer(response, writer, |_| {}).await?;
 
-        let mut async_read = std::pin::pin!(async_read);
-
-        io::copy(&mut async_read, writer).await?;
+        Ok(status_code.as_u16())
+    }
 
+    async fn response_data_to_writer_with_checksum<T: tokio::io::AsyncWrite + Send + Unpin>(
+        &self,
+        writer: &mut T,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<u16, S3Error> {
+        let response = self.response().await?;
+        let status_code = response.status();
+        // Objects uploaded in parts have a checksum of the part checksums,
+        // `<checksum>-<parts>`, which can not be verified from the content.
+        let expected = response
+            .headers()
+            .get(algorithm.header_name())
+            .map(|value| value.to_str())
+            .transpose()?
+            .filter(|value| !value.contains('-'))
+            .map(str::to_string);
+
+        let mut checksum = algorithm.hasher();
+        copy_to_writer(response, writer, |data| checksum.update(data)).await?;
+
+        if let Some(expected) = expected {
+            let computed = checksum.finalize();
+            if computed != expected {
+                return Err(S3Error::ChecksumMismatch {
+                    algorithm,
+                    expected,
+                    computed,
+                });
+            }
+        }
         Ok(status_code.as_u16())
     }
 
@@ -177,7 +200,7 @@ impl<'a> Request for HyperRequest<'a> {
         self.bucket.clone()
     }
 
-    fn command(&self) -> Command {
+    fn command(&self) -> Command<'_> {
         self.command.clone()
     }
 
@@ -186,13 +209,92 @@ impl<'a> Request for HyperRequest<'a> {
     }
 }
 
+/// Copy the body of `response` to `writer`, passing every chunk to `inspect`.
+async fn copy_to_writer<T: tokio::io::AsyncWrite + Send + Unpin>(
+    response: http::Response<Body>,
+    writer: &mut T,
+    mut inspect: impl FnMut(&Bytes) + Send,
+) -> Result<(), S3Error> {
+    let stream = BodyStream::new(response.into_body());
+
+    let stream_of_bytes = stream
+        .try_filter_map(
+            |frame: hyper::body::Frame<Bytes>| async move { Ok(frame.into_data().ok()) },
+        )
+        .inspect_ok(|data| inspect(data))
+        .map_err(io::Error::other);
+
+    let async_read = tokio_util::io::StreamReader::new(stream_of_bytes);
+    let buffer_size = 512 * 1024; //512 KiB
+    let async_read = tokio::io::BufReader::with_capacity(buffer_size, async_read);
+
+    let mut async_read = std::pin::pin!(async_read);
+
+    io::copy(&mut async_read, writer).await?;
+    Ok(())
+}
+
 impl<'a> HyperRequest<'a> {
+    /// Sign and send the request once, without any retries.
+    async fn send(&self) -> Result<http::Response<Body>, S3Error> {
+        self.bucket.refresh_credentials().await?;
+        // Build headers
+        let headers = match self.headers() {
+            Ok(headers) => headers,
+            Err(e) => return Err(e),
+        };
+        let transport = self.bucket.transport();
+
+        let method = match self.command.http_verb() {
+            HttpMethod::Delete => http::Method::DELETE,
+            HttpMethod::Get => http::Method::GET,
+            HttpMethod::Post => http::Method::POST,
+            HttpMethod::Put => http::Method::PUT,
+            HttpMethod::Head => http::Method::HEAD,
+        };
+
+        let request: http::Request<Body> = {
+            let mut request = http::Request::builder()
+                .method(method)
+                .uri(self.url()?.as_str());
+
+            for (header, value) in headers.iter() {
+                request = request.header(header, value);
+            }
+            request.body(self.request_body(&headers)?)?
+        };
+        #[cfg(feature = "tracing")]
+        let span = span!(
+            Level::DEBUG,
+            "rust-s3-async",
+            bucket = self.bucket.name(),
+            command = self.command.to_string(),
+            path = self.path,
+            second = self.datetime.second(),
+            minute = self.datetime.minute(),
+            hour = self.datetime.hour(),
+            day = self.datetime.day(),
+            month = self.datetime.month() as u8,
+            year = self.datetime.year()
+        );
+        #[cfg(feature = "tracing")]
+        let _enter = span.enter();
+        let response = match self.bucket.request_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, transport.send(request)).await??,
+            None => transport.send(request).await?,
+        };
+
+        #[cfg(feature = "tracing")]
+        event!(Level::DEBUG, status_code = response.status().as_u16(),);
+
+        Ok(response)
+    }
+
     pub fn new(
         bucket: &'a Bucket,
         path: &'a str,
         command: Command<'a>,
     ) -> Result<HyperRequest<'a>, S3Error> {
-        bucket.credentials_refresh()?;
         Ok(Self {
             bucket,
             path,
@@ -206,10 +308,22 @@ impl<'a> HyperRequest<'a> {
 mod tests {
     use crate::bucket::Bucket;
     use crate::command::Command;
+    use crate::error::S3Error;
     use crate::request::tokio_backend::HyperRequest;
     use crate::request::Request;
+    use crate::retry::RetryPolicy;
     use awscreds::Credentials;
+    use bytes::Bytes;
     use http::header::{HOST, RANGE};
+    use http_body_util::Full;
+    use hyper::server::conn::http1;
+    use hyper::service::service_fn;
+    use hyper_util::rt::TokioIo;
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::net::TcpListener;
 
     // Fake keys - otherwise using Credentials::default will use actual user
     // credentials if they exist.
@@ -315,4 +429,155 @@ mod tests {
         let range = headers.get(RANGE).unwrap();
         assert_eq!(range, "bytes=0-1");
     }
+
+    const SLOW_DOWN: &str = "<Error><Code>SlowDown</Code><Message>Please reduce your request rate.</Message><RequestId>1</RequestId></Error>";
+
+    /// Serve plain HTTP, answering the first `failures` requests with `status`
+    /// and `body`, and `ok` afterwards. Returns the address and the
+    /// `x-amz-date` header of every request received.
+    async fn serve_failing(
+        failures: usize,
+        status: u16,
+        body: &'static str,
+    ) -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let requests = seen.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let requests = requests.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(move |req: hyper::Request<hyper::body::Incoming>| {
+                        let requests = requests.clone();
+                        async move {
+                            let mut requests = requests.lock().unwrap();
+                            requests
+                                .push(req.headers()["x-amz-date"].to_str().unwrap().to_string());
+                            let response = if requests.len() <= failures {
+                                hyper::Response::builder()
+                                    .status(status)
+                                    .body(Full::new(Bytes::from_static(body.as_bytes())))
+                            } else {
+                                hyper::Response::builder().body(Full::new(Bytes::from("ok")))
+                            };
+                            Ok::<_, Infallible>(response.unwrap())
+                        }
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+
+        (addr, seen)
+    }
+
+    fn local_bucket(addr: SocketAddr, policy: RetryPolicy) -> Bucket {
+        let region = format!("http://{}", addr).parse().unwrap();
+        Bucket::new("rust-s3", region, fake_credentials())
+            .unwrap()
+            .with_path_style()
+            .with_retry_policy(policy)
+    }
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::default()
+            .with_max_attempts(max_attempts)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(10))
+    }
+
+    #[tokio::test]
+    async fn retries_until_success() {
+        let (addr, seen) = serve_failing(2, 503, SLOW_DOWN).await;
+        let bucket = local_bucket(addr, fast_policy(3));
+
+        let response_data = bucket.get_object("/test.file").await.unwrap();
+        assert_eq!(response_data.status_code(), 200);
+        assert_eq!(response_data.as_slice(), b"ok");
+        assert_eq!(seen.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let (addr, seen) = serve_failing(5, 500, "").await;
+        let bucket = local_bucket(addr, fast_policy(3));
+
+        let result = bucket.get_object("/test.file").await;
+        if cfg!(feature = "fail-on-err") {
+            assert!(result.is_err());
+        } else {
+            assert_eq!(result.unwrap().status_code(), 500);
+        }
+        assert_eq!(seen.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_client_errors() {
+        let (addr, seen) = serve_failing(1, 404, "").await;
+        let bucket = local_bucket(addr, fast_policy(3));
+
+        let result = bucket.get_object("/test.file").await;
+        if cfg!(feature = "fail-on-err") {
+            assert!(result.unwrap_err().is_not_found());
+        } else {
+            assert_eq!(result.unwrap().status_code(), 404);
+        }
+        assert_eq!(seen.lock().unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "fail-on-err")]
+    #[tokio::test]
+    async fn retries_error_codes() {
+        // RequestTimeout comes back as a 400
+        let request_timeout = "<Error><Code>RequestTimeout</Code><Message>Timed out</Message><RequestId>1</RequestId></Error>";
+        let (addr, seen) = serve_failing(1, 400, request_timeout).await;
+        let bucket = local_bucket(addr, fast_policy(3));
+
+        let response_data = bucket.get_object("/test.file").await.unwrap();
+        assert_eq!(response_data.status_code(), 200);
+        assert_eq!(seen.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_are_signed_again() {
+        let (addr, seen) = serve_failing(1, 503, SLOW_DOWN).await;
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(1100), Duration::from_secs(2))
+            .with_jitter(false);
+        let bucket = local_bucket(addr, policy);
+
+        bucket.get_object("/test.file").await.unwrap();
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_ne!(seen[0], seen[1]);
+    }
+
+    #[tokio::test]
+    async fn request_timeout_is_enforced() {
+        // Accept connections, but never answer
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                streams.push(stream);
+            }
+        });
+
+        let bucket = Bucket::builder("rust-s3", format!("http://{}", addr).parse().unwrap())
+            .credentials(fake_credentials())
+            .path_style()
+            .request_timeout(Duration::from_millis(100))
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+
+        let result = bucket.get_object("/test.file").await;
+        assert!(matches!(result, Err(S3Error::Timeout(_))));
+    }
 }
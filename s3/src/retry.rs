@@ -0,0 +1,182 @@
+//! Retry policy applied to every request sent by a [`Bucket`](crate::Bucket).
+
+use std::time::Duration;
+
+use crate::error::S3Error;
+
+/// HTTP statuses retried by [`RetryPolicy::default`].
+pub const DEFAULT_RETRYABLE_STATUSES: &[u16] = &[429, 500, 502, 503, 504];
+
+/// S3 error codes retried by [`RetryPolicy::default`], whatever the HTTP status.
+pub const DEFAULT_RETRYABLE_CODES: &[&str] = &[
+    "InternalError",
+    "RequestTimeout",
+    "RequestTimeTooSkewed",
+    "ServiceUnavailable",
+    "SlowDown",
+    "Throttling",
+    "ThrottlingException",
+];
+
+/// Decides whether and when a failed request is sent again.
+///
+/// Every attempt is signed again with a fresh timestamp. The delay before
+/// attempt `n + 1` is `base_backoff * 2^(n - 1)`, capped at `max_backoff`,
+/// and with `jitter` enabled a uniformly random duration between zero and
+/// that value is used instead.
+///
+/// Transport errors (connection resets, timeouts, ...) are always retried.
+/// Error codes are only looked at with the `fail-on-err` feature, as the
+/// response body is otherwise handed to the caller untouched.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use s3::RetryPolicy;
+///
+/// let policy = RetryPolicy::default()
+///     .with_max_attempts(5)
+///     .with_backoff(Duration::from_millis(50), Duration::from_secs(5));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub base_backoff: Duration,
+    /// Upper bound for any single delay.
+    pub max_backoff: Duration,
+    /// Randomize delays ("full jitter") to spread out retries of concurrent requests.
+    pub jitter: bool,
+    /// HTTP statuses that are retried.
+    pub retryable_statuses: Vec<u16>,
+    /// S3 error codes (`<Code>` in the error body) that are retried.
+    pub retryable_codes: Vec<String>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(20),
+            jitter: true,
+            retryable_statuses: DEFAULT_RETRYABLE_STATUSES.to_vec(),
+            retryable_codes: DEFAULT_RETRYABLE_CODES
+                .iter()
+                .map(|code| code.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_backoff(mut self, base_backoff: Duration, max_backoff: Duration) -> Self {
+        self.base_backoff = base_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn with_retryable_statuses(mut self, statuses: Vec<u16>) -> Self {
+        self.retryable_statuses = statuses;
+        self
+    }
+
+    pub fn with_retryable_codes(mut self, codes: Vec<String>) -> Self {
+        self.retryable_codes = codes;
+        self
+    }
+
+    /// Whether another attempt may follow attempt number `attempt` (1-based).
+    pub fn has_attempts_left(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    pub fn is_retryable_status(&self, status: u16) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    pub fn is_retryable_code(&self, code: &str) -> bool {
+        self.retryable_codes.iter().any(|c| c == code)
+    }
+
+    /// Whether an error raised while sending the request, before any
+    /// response was received, is worth retrying.
+    pub fn is_retryable_error(&self, error: &S3Error) -> bool {
+        matches!(
+            error,
+            S3Error::Hyper(_) | S3Error::HyperUtil(_) | S3Error::Io(_) | S3Error::Timeout(_)
+        )
+    }
+
+    /// The delay to wait after attempt number `attempt` (1-based) failed.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .base_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+        if self.jitter {
+            backoff.mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(500))
+            .with_jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+        assert_eq!(policy.backoff(100), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn jittered_backoff_stays_in_bounds() {
+        let policy =
+            RetryPolicy::default().with_backoff(Duration::from_millis(100), Duration::from_secs(1));
+        for _ in 0..100 {
+            assert!(policy.backoff(2) <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn classifies_statuses_and_codes() {
+        let policy = RetryPolicy::default();
+        assert!(policy.is_retryable_status(503));
+        assert!(!policy.is_retryable_status(404));
+        assert!(policy.is_retryable_code("SlowDown"));
+        assert!(!policy.is_retryable_code("NoSuchKey"));
+        assert!(!RetryPolicy::none().has_attempts_left(1));
+    }
+}
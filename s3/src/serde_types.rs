@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::checksum::ChecksumAlgorithm;
+
 #[derive(Deserialize, Debug)]
 pub struct InitiateMultipartUploadResponse {
     #[serde(rename = "Bucket")]
@@ -85,11 +87,11 @@ impl fmt::Display for CompleteMultipartUploadData {
 
 impl CompleteMultipartUploadData {
     pub fn len(&self) -> usize {
-        self.to_string().as_bytes().len()
+        self.to_string().len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.to_string().as_bytes().len() == 0
+        self.to_string().len() == 0
     }
 }
 
@@ -98,12 +100,62 @@ pub struct CompleteMultipartUploadData {
     pub parts: Vec<Part>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Part {
     #[serde(rename = "PartNumber")]
     pub part_number: u32,
     #[serde(rename = "ETag")]
     pub etag: String,
+    #[serde(
+        rename = "ChecksumCRC32",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_crc32: Option<String>,
+    #[serde(
+        rename = "ChecksumCRC32C",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_crc32c: Option<String>,
+    #[serde(
+        rename = "ChecksumSHA1",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_sha1: Option<String>,
+    #[serde(
+        rename = "ChecksumSHA256",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_sha256: Option<String>,
+}
+
+impl Part {
+    pub fn new(part_number: u32, etag: impl Into<String>) -> Self {
+        Part {
+            part_number,
+            etag: etag.into(),
+            checksum_crc32: None,
+            checksum_crc32c: None,
+            checksum_sha1: None,
+            checksum_sha256: None,
+        }
+    }
+
+    /// Record the checksum the part was uploaded with, uploads initiated
+    /// with a checksum algorithm need it to complete.
+    pub fn with_checksum(mut self, algorithm: ChecksumAlgorithm, checksum: String) -> Self {
+        let field = match algorithm {
+            ChecksumAlgorithm::Crc32 => &mut self.checksum_crc32,
+            ChecksumAlgorithm::Crc32c => &mut self.checksum_crc32c,
+            ChecksumAlgorithm::Sha1 => &mut self.checksum_sha1,
+            ChecksumAlgorithm::Sha256 => &mut self.checksum_sha256,
+        };
+        *field = Some(checksum);
+        self
+    }
 }
 
 impl fmt::Display for Part {
@@ -111,10 +163,161 @@ impl fmt::Display for Part {
         write!(f, "<Part>").expect("Can't fail");
         write!(f, "<PartNumber>{}</PartNumber>", self.part_number).expect("Can't fail");
         write!(f, "<ETag>{}</ETag>", self.etag).expect("Can't fail");
+        let checksums = [
+            ("ChecksumCRC32", &self.checksum_crc32),
+            ("ChecksumCRC32C", &self.checksum_crc32c),
+            ("ChecksumSHA1", &self.checksum_sha1),
+            ("ChecksumSHA256", &self.checksum_sha256),
+        ];
+        for (name, checksum) in checksums {
+            if let Some(checksum) = checksum {
+                write!(f, "<{name}>{checksum}</{name}>").expect("Can't fail");
+            }
+        }
         write!(f, "</Part>")
     }
 }
 
+/// The parsed result of a `CopyObject`
+#[derive(Deserialize, Debug, Clone)]
+pub struct CopyObjectResult {
+    #[serde(rename = "ETag")]
+    /// Entity tag of the copy.
+    pub etag: String,
+    #[serde(rename = "LastModified")]
+    /// Date and time at which the copy was created.
+    pub last_modified: DateTime,
+}
+
+/// The parsed result of an `UploadPartCopy`
+#[derive(Deserialize, Debug, Clone)]
+pub struct CopyPartResult {
+    #[serde(rename = "ETag")]
+    /// Entity tag of the copied part.
+    pub etag: String,
+    #[serde(rename = "LastModified")]
+    /// Date and time at which the part was copied.
+    pub last_modified: DateTime,
+}
+
+/// An object to delete with `DeleteObjects`, the current version unless `version_id` is set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectIdentifier {
+    pub key: String,
+    pub version_id: Option<String>,
+}
+
+impl ObjectIdentifier {
+    pub fn new(key: impl Into<String>) -> Self {
+        ObjectIdentifier {
+            key: key.into(),
+            version_id: None,
+        }
+    }
+
+    pub fn with_version_id(mut self, version_id: impl Into<String>) -> Self {
+        self.version_id = Some(version_id.into());
+        self
+    }
+}
+
+impl From<&str> for ObjectIdentifier {
+    fn from(key: &str) -> Self {
+        ObjectIdentifier::new(key)
+    }
+}
+
+impl From<String> for ObjectIdentifier {
+    fn from(key: String) -> Self {
+        ObjectIdentifier::new(key)
+    }
+}
+
+impl fmt::Display for ObjectIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = self.key.strip_prefix('/').unwrap_or(&self.key);
+        write!(f, "<Object><Key>{}</Key>", quick_xml::escape::escape(key))?;
+        if let Some(version_id) = &self.version_id {
+            write!(
+                f,
+                "<VersionId>{}</VersionId>",
+                quick_xml::escape::escape(version_id.as_str())
+            )?;
+        }
+        write!(f, "</Object>")
+    }
+}
+
+/// The body of a `DeleteObjects` request
+#[derive(Debug, Clone)]
+pub struct DeleteObjectsData {
+    pub objects: Vec<ObjectIdentifier>,
+    /// Only report the objects that could not be deleted.
+    pub quiet: bool,
+}
+
+impl fmt::Display for DeleteObjectsData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<Delete>")?;
+        if self.quiet {
+            write!(f, "<Quiet>true</Quiet>")?;
+        }
+        for object in &self.objects {
+            write!(f, "{}", object)?;
+        }
+        write!(f, "</Delete>")
+    }
+}
+
+/// An object removed by `DeleteObjects`
+#[derive(Deserialize, Debug, Clone)]
+pub struct DeletedObject {
+    #[serde(rename = "Key")]
+    /// The object's key
+    pub key: String,
+    #[serde(rename = "VersionId")]
+    /// The version that was deleted, if one was given.
+    pub version_id: Option<String>,
+    #[serde(
+        rename = "DeleteMarker",
+        default,
+        deserialize_with = "super::deserializer::bool_deserializer"
+    )]
+    /// Whether a delete marker was created or deleted.
+    pub delete_marker: bool,
+    #[serde(rename = "DeleteMarkerVersionId")]
+    /// Version ID of the delete marker that was created or deleted.
+    pub delete_marker_version_id: Option<String>,
+}
+
+/// An object `DeleteObjects` failed to remove
+#[derive(Deserialize, Debug, Clone)]
+pub struct DeleteError {
+    #[serde(rename = "Key")]
+    /// The object's key
+    pub key: String,
+    #[serde(rename = "VersionId")]
+    /// The version that failed to be deleted, if one was given.
+    pub version_id: Option<String>,
+    #[serde(rename = "Code")]
+    /// S3 error code, e.g. `AccessDenied`.
+    pub code: String,
+    #[serde(rename = "Message")]
+    /// Human readable description of the error.
+    pub message: String,
+}
+
+/// The parsed result of a `DeleteObjects`
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct DeleteObjectsResult {
+    #[serde(rename = "Deleted", default)]
+    /// Objects that were deleted, empty in quiet mode.
+    pub deleted: Vec<DeletedObject>,
+    #[serde(rename = "Error", default)]
+    /// Objects that could not be deleted.
+    pub errors: Vec<DeleteError>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct BucketLocationResult {
     #[serde(rename = "$value")]
@@ -172,6 +375,27 @@ pub struct ListBucketResult {
     pub common_prefixes: Option<Vec<CommonPrefix>>,
 }
 
+impl ListBucketResult {
+    /// The marker or continuation token to request the next page with, none on the last page.
+    ///
+    /// `ListObjects` (V1) only returns a `NextMarker` along with a delimiter, otherwise the
+    /// last key listed is the marker.
+    pub(crate) fn next_page_token(&self) -> Option<String> {
+        if !self.is_truncated {
+            return None;
+        }
+        self.next_continuation_token.clone().or_else(|| {
+            let last_key = self.contents.last().map(|object| &object.key);
+            let last_prefix = self
+                .common_prefixes
+                .as_ref()
+                .and_then(|prefixes| prefixes.last())
+                .map(|prefix| &prefix.prefix);
+            last_key.max(last_prefix).cloned()
+        })
+    }
+}
+
 /// The parsed result of a s3 bucket listing of uploads
 #[derive(Deserialize, Debug, Clone)]
 pub struct ListMultipartUploadsResult {
@@ -211,6 +435,235 @@ pub struct ListMultipartUploadsResult {
     pub common_prefixes: Option<Vec<CommonPrefix>>,
 }
 
+/// A part of an in-progress multipart upload, as listed in a `ListPartsResult`
+#[derive(Deserialize, Debug, Clone)]
+pub struct UploadedPart {
+    #[serde(rename = "PartNumber")]
+    /// Part number identifying the part.
+    pub part_number: u32,
+    #[serde(rename = "ETag")]
+    /// Entity tag returned when the part was uploaded.
+    pub etag: String,
+    #[serde(rename = "Size")]
+    /// Size in bytes of the uploaded part data.
+    pub size: u64,
+    #[serde(rename = "LastModified")]
+    /// Date and time at which the part was uploaded.
+    pub last_modified: DateTime,
+    #[serde(rename = "ChecksumCRC32")]
+    pub checksum_crc32: Option<String>,
+    #[serde(rename = "ChecksumCRC32C")]
+    pub checksum_crc32c: Option<String>,
+    #[serde(rename = "ChecksumSHA1")]
+    pub checksum_sha1: Option<String>,
+    #[serde(rename = "ChecksumSHA256")]
+    pub checksum_sha256: Option<String>,
+}
+
+impl From<UploadedPart> for Part {
+    fn from(part: UploadedPart) -> Self {
+        Part {
+            part_number: part.part_number,
+            etag: part.etag,
+            checksum_crc32: part.checksum_crc32,
+            checksum_crc32c: part.checksum_crc32c,
+            checksum_sha1: part.checksum_sha1,
+            checksum_sha256: part.checksum_sha256,
+        }
+    }
+}
+
+/// The parsed result of listing the parts of a multipart upload
+#[derive(Deserialize, Debug, Clone)]
+pub struct ListPartsResult {
+    #[serde(rename = "Bucket")]
+    /// Name of the bucket.
+    pub name: String,
+    #[serde(rename = "Key")]
+    /// Object key for which the multipart upload was initiated.
+    pub key: String,
+    #[serde(rename = "UploadId")]
+    /// Upload ID identifying the multipart upload whose parts are being listed.
+    pub upload_id: String,
+    #[serde(rename = "PartNumberMarker")]
+    /// The part number after which the listing begins.
+    pub part_number_marker: Option<u32>,
+    #[serde(rename = "NextPartNumberMarker")]
+    /// When the response is truncated, the part number to use as a marker in the subsequent
+    /// request to get the next set of parts.
+    pub next_part_number_marker: Option<u32>,
+    #[serde(rename = "MaxParts")]
+    /// Maximum number of parts that were allowed in the response.
+    pub max_parts: Option<u32>,
+    #[serde(rename = "StorageClass")]
+    /// Storage class of the object being uploaded.
+    pub storage_class: Option<String>,
+    #[serde(rename = "Owner")]
+    /// Owner of the object being uploaded.
+    pub owner: Option<Owner>,
+    #[serde(
+        rename = "IsTruncated",
+        deserialize_with = "super::deserializer::bool_deserializer"
+    )]
+    /// Specifies whether (true) or not (false) all of the parts were returned.
+    pub is_truncated: bool,
+    #[serde(rename = "Part", default)]
+    /// The parts uploaded so far, in ascending order of part number.
+    pub parts: Vec<UploadedPart>,
+}
+
+/// A version of an object in a `ListVersionsResult`
+#[derive(Deserialize, Debug, Clone)]
+pub struct ObjectVersion {
+    #[serde(rename = "Key")]
+    /// The object's key
+    pub key: String,
+    #[serde(rename = "VersionId")]
+    /// Version ID of the object, `null` for objects stored before versioning was enabled.
+    pub version_id: String,
+    #[serde(
+        rename = "IsLatest",
+        deserialize_with = "super::deserializer::bool_deserializer"
+    )]
+    /// Whether this is the current version of the object.
+    pub is_latest: bool,
+    #[serde(rename = "LastModified")]
+    /// Date and time the version was created.
+    pub last_modified: DateTime,
+    #[serde(rename = "ETag")]
+    /// The entity tag of the version.
+    pub e_tag: Option<String>,
+    #[serde(rename = "Size")]
+    /// Size in bytes of the version.
+    pub size: u64,
+    #[serde(rename = "StorageClass")]
+    /// STANDARD | STANDARD_IA | REDUCED_REDUNDANCY | GLACIER
+    pub storage_class: Option<String>,
+    #[serde(rename = "Owner")]
+    /// Owner of the version
+    pub owner: Option<Owner>,
+}
+
+/// A delete marker in a `ListVersionsResult`
+#[derive(Deserialize, Debug, Clone)]
+pub struct DeleteMarkerEntry {
+    #[serde(rename = "Key")]
+    /// The object's key
+    pub key: String,
+    #[serde(rename = "VersionId")]
+    /// Version ID of the delete marker.
+    pub version_id: String,
+    #[serde(
+        rename = "IsLatest",
+        deserialize_with = "super::deserializer::bool_deserializer"
+    )]
+    /// Whether the delete marker is the current version of the object.
+    pub is_latest: bool,
+    #[serde(rename = "LastModified")]
+    /// Date and time the delete marker was created.
+    pub last_modified: DateTime,
+    #[serde(rename = "Owner")]
+    /// Owner of the delete marker
+    pub owner: Option<Owner>,
+}
+
+/// The parsed result of a s3 bucket listing of object versions
+#[derive(Deserialize, Debug, Clone)]
+pub struct ListVersionsResult {
+    #[serde(rename = "Name")]
+    /// Name of the bucket.
+    pub name: String,
+    #[serde(rename = "Prefix")]
+    /// Keys that begin with the indicated prefix.
+    pub prefix: Option<String>,
+    #[serde(rename = "Delimiter")]
+    /// A delimiter is a character used to group keys.
+    pub delimiter: Option<String>,
+    #[serde(rename = "KeyMarker")]
+    /// The key after which the listing begins.
+    pub key_marker: Option<String>,
+    #[serde(rename = "VersionIdMarker")]
+    /// The version after which the listing begins.
+    pub version_id_marker: Option<String>,
+    #[serde(rename = "NextKeyMarker")]
+    /// When the response is truncated, the key marker to use in the subsequent request.
+    pub next_key_marker: Option<String>,
+    #[serde(rename = "NextVersionIdMarker")]
+    /// When the response is truncated, the version ID marker to use in the subsequent request.
+    pub next_version_id_marker: Option<String>,
+    #[serde(rename = "MaxKeys")]
+    /// Maximum number of versions and delete markers that were allowed in the response.
+    pub max_keys: Option<usize>,
+    #[serde(
+        rename = "IsTruncated",
+        deserialize_with = "super::deserializer::bool_deserializer"
+    )]
+    /// Specifies whether (true) or not (false) all of the results were returned.
+    pub is_truncated: bool,
+    #[serde(rename = "Version", default)]
+    /// Metadata about each version returned.
+    pub versions: Vec<ObjectVersion>,
+    #[serde(rename = "DeleteMarker", default)]
+    /// Metadata about each delete marker returned.
+    pub delete_markers: Vec<DeleteMarkerEntry>,
+    #[serde(rename = "CommonPrefixes", default)]
+    /// All of the keys rolled up into a common prefix count as a single return when
+    /// calculating the number of returns.
+    pub common_prefixes: Option<Vec<CommonPrefix>>,
+}
+
+/// Whether versioning is enabled on a bucket
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersioningStatus {
+    Enabled,
+    Suspended,
+}
+
+impl fmt::Display for VersioningStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersioningStatus::Enabled => write!(f, "Enabled"),
+            VersioningStatus::Suspended => write!(f, "Suspended"),
+        }
+    }
+}
+
+/// The versioning state of a bucket
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersioningConfiguration {
+    #[serde(rename = "Status")]
+    /// Absent if versioning was never enabled on the bucket.
+    pub status: Option<VersioningStatus>,
+    #[serde(rename = "MfaDelete")]
+    /// Whether MFA delete is enabled, only returned if it was ever configured.
+    pub mfa_delete: Option<String>,
+}
+
+impl VersioningConfiguration {
+    pub fn new(status: VersioningStatus) -> Self {
+        VersioningConfiguration {
+            status: Some(status),
+            mfa_delete: None,
+        }
+    }
+}
+
+impl fmt::Display for VersioningConfiguration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<VersioningConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">"
+        )?;
+        if let Some(status) = self.status {
+            write!(f, "<Status>{}</Status>", status)?;
+        }
+        if let Some(mfa_delete) = &self.mfa_delete {
+            write!(f, "<MfaDelete>{}</MfaDelete>", mfa_delete)?;
+        }
+        write!(f, "</VersioningConfiguration>")
+    }
+}
+
 /// `CommonPrefix` is used to group keys
 #[derive(Deserialize, Debug, Clone)]
 pub struct CommonPrefix {
@@ -313,14 +766,20 @@ pub struct HeadObjectResult {
     pub website_redirect_location: Option<String>,
 }
 
+/// The XML body of an S3 error response
 #[derive(Deserialize, Debug)]
+#[serde(rename = "Error")]
 pub struct AwsError {
     #[serde(rename = "Code")]
     pub code: String,
-    #[serde(rename = "Message")]
+    #[serde(rename = "Message", default)]
     pub message: String,
-    #[serde(rename = "RequestId")]
+    #[serde(rename = "RequestId", default)]
     pub request_id: String,
+    #[serde(rename = "HostId")]
+    pub host_id: Option<String>,
+    #[serde(rename = "Resource")]
+    pub resource: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
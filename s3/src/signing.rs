@@ -14,7 +14,7 @@ use url::Url;
 
 use crate::error::S3Error;
 use crate::region::Region;
-use crate::LONG_DATETIME;
+use crate::{EMPTY_PAYLOAD_SHA, LONG_DATETIME};
 
 use std::fmt::Write as _;
 
@@ -202,6 +202,102 @@ pub fn authorization_header(
     ))
 }
 
+/// `x-amz-content-sha256` of a payload sent in signed aws-chunked encoding.
+pub const STREAMING_PAYLOAD: &str = "STREAMING-AWS4-HMAC-SHA256-PAYLOAD";
+/// `x-amz-content-sha256` of a payload sent in signed aws-chunked encoding,
+/// followed by signed trailing headers.
+pub const STREAMING_PAYLOAD_TRAILER: &str = "STREAMING-AWS4-HMAC-SHA256-PAYLOAD-TRAILER";
+
+/// Length of a hex encoded signature.
+const SIGNATURE_LEN: u64 = 64;
+
+/// Signs the chunks of a payload sent in aws-chunked encoding.
+///
+/// Every chunk signature covers the chunk and the previous signature,
+/// starting with the seed signature from the `Authorization` header, so
+/// chunks can neither be altered nor reordered.
+/// See [the docs](https://docs.aws.amazon.com/AmazonS3/latest/API/sigv4-streaming.html).
+#[derive(Clone, Debug)]
+pub struct ChunkSigner {
+    signing_key: Vec<u8>,
+    timestamp: String,
+    scope: String,
+    previous_signature: String,
+}
+
+impl ChunkSigner {
+    pub fn new(
+        signing_key: Vec<u8>,
+        datetime: &OffsetDateTime,
+        region: &Region,
+        seed_signature: &str,
+    ) -> Result<Self, S3Error> {
+        Ok(ChunkSigner {
+            signing_key,
+            timestamp: datetime.format(LONG_DATETIME)?,
+            scope: scope_string(datetime, region)?,
+            previous_signature: seed_signature.to_string(),
+        })
+    }
+
+    fn sign(&mut self, algorithm: &str, hashes: &str) -> Result<String, S3Error> {
+        let string_to_sign = format!(
+            "{algorithm}\n{timestamp}\n{scope}\n{previous}\n{hashes}",
+            timestamp = self.timestamp,
+            scope = self.scope,
+            previous = self.previous_signature,
+        );
+        let mut hmac = HmacSha256::new_from_slice(&self.signing_key)?;
+        hmac.update(string_to_sign.as_bytes());
+        self.previous_signature = hex::encode(hmac.finalize().into_bytes());
+        Ok(self.previous_signature.clone())
+    }
+
+    /// Sign the next chunk, an empty one marks the end of the payload.
+    pub fn sign_chunk(&mut self, chunk: &[u8]) -> Result<String, S3Error> {
+        let hashes = format!(
+            "{}\n{}",
+            EMPTY_PAYLOAD_SHA,
+            hex::encode(Sha256::digest(chunk))
+        );
+        self.sign("AWS4-HMAC-SHA256-PAYLOAD", &hashes)
+    }
+
+    /// Sign the trailing headers, each formatted as `name:value\n`, sent
+    /// after the final chunk.
+    pub fn sign_trailer(&mut self, trailer: &str) -> Result<String, S3Error> {
+        let hash = hex::encode(Sha256::digest(trailer.as_bytes()));
+        self.sign("AWS4-HMAC-SHA256-TRAILER", &hash)
+    }
+}
+
+/// Frame a chunk of an aws-chunked payload.
+pub fn chunk_header(chunk_len: usize, signature: &str) -> String {
+    format!("{:x};chunk-signature={}\r\n", chunk_len, signature)
+}
+
+/// The `Content-Length` of a payload of `decoded_len` bytes sent in signed
+/// aws-chunked encoding, in chunks of `chunk_size` bytes. `trailer_len` is
+/// the length of the trailing `name:value` header, if any.
+pub fn aws_chunked_len(decoded_len: u64, chunk_size: u64, trailer_len: Option<u64>) -> u64 {
+    let framed = |len: u64| {
+        let hex_len = format!("{:x}", len).len() as u64;
+        hex_len + ";chunk-signature=".len() as u64 + SIGNATURE_LEN + 2 + len + 2
+    };
+    let full_chunks = decoded_len / chunk_size;
+    let remainder = decoded_len % chunk_size;
+    let mut len = full_chunks * framed(chunk_size);
+    if remainder > 0 {
+        len += framed(remainder);
+    }
+    // The final, empty chunk, its data's CRLF doubling as the end of the payload
+    len += framed(0);
+    if let Some(trailer_len) = trailer_len {
+        len += trailer_len + 2 + "x-amz-trailer-signature:".len() as u64 + SIGNATURE_LEN + 2;
+    }
+    len
+}
+
 pub fn authorization_query_params_no_sig(
     access_key: &str,
     datetime: &OffsetDateTime,
@@ -272,7 +368,7 @@ mod tests {
     use std::convert::TryInto;
     use std::str;
 
-    use http::header::{HeaderName, HOST, RANGE};
+    use http::header::{HeaderName, CONTENT_ENCODING, CONTENT_LENGTH, HOST, RANGE};
     use http::HeaderMap;
     use time::Date;
     use url::Url;
@@ -428,6 +524,118 @@ mod tests {
         assert_eq!(expected, hex::encode(hmac.finalize().into_bytes()));
     }
 
+    fn streaming_headers(content_sha256: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_ENCODING, "aws-chunked".parse().unwrap());
+        headers.insert(HOST, "s3.amazonaws.com".parse().unwrap());
+        headers.insert(
+            HeaderName::from_static("x-amz-content-sha256"),
+            content_sha256.parse().unwrap(),
+        );
+        headers.insert(
+            HeaderName::from_static("x-amz-date"),
+            "20130524T000000Z".parse().unwrap(),
+        );
+        headers.insert(
+            HeaderName::from_static("x-amz-decoded-content-length"),
+            "66560".parse().unwrap(),
+        );
+        headers.insert(
+            HeaderName::from_static("x-amz-storage-class"),
+            "REDUCED_REDUNDANCY".parse().unwrap(),
+        );
+        headers
+    }
+
+    /// Sign the example requests from
+    /// https://docs.aws.amazon.com/AmazonS3/latest/API/sigv4-streaming.html
+    fn streaming_seed_signature(
+        headers: &HeaderMap,
+        content_sha256: &str,
+    ) -> (ChunkSigner, String) {
+        let url = Url::parse("https://s3.amazonaws.com/examplebucket/chunkObject.txt").unwrap();
+        let datetime = Date::from_calendar_date(2013, 5.try_into().unwrap(), 24)
+            .unwrap()
+            .with_hms(0, 0, 0)
+            .unwrap()
+            .assume_utc();
+        let region = "us-east-1".parse().unwrap();
+        let secret = "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY";
+        let signing_key = signing_key(&datetime, secret, &region, "s3").unwrap();
+
+        let canonical = canonical_request("PUT", &url, headers, content_sha256).unwrap();
+        let string_to_sign = string_to_sign(&datetime, &region, &canonical).unwrap();
+        let mut hmac = HmacSha256::new_from_slice(&signing_key).unwrap();
+        hmac.update(string_to_sign.as_bytes());
+        let seed_signature = hex::encode(hmac.finalize().into_bytes());
+
+        let signer = ChunkSigner::new(signing_key, &datetime, &region, &seed_signature).unwrap();
+        (signer, seed_signature)
+    }
+
+    #[test]
+    fn test_streaming_signing() {
+        let mut headers = streaming_headers(STREAMING_PAYLOAD);
+        let content_length = aws_chunked_len(66560, 65536, None);
+        assert_eq!(content_length, 66824);
+        headers.insert(CONTENT_LENGTH, content_length.into());
+
+        let (mut signer, seed_signature) = streaming_seed_signature(&headers, STREAMING_PAYLOAD);
+        assert_eq!(
+            seed_signature,
+            "4f232c4386841ef735655705268965c44a0e4690baa4adea153f7db9fa80a0a9"
+        );
+        assert_eq!(
+            signer.sign_chunk(&[b'a'; 65536]).unwrap(),
+            "ad80c730a21e5b8d04586a2213dd63b9a0e99e0e2307b0ade35a65485a288648"
+        );
+        assert_eq!(
+            signer.sign_chunk(&[b'a'; 1024]).unwrap(),
+            "0055627c9e194cb4542bae2aa5492e3c1575bbb81b612b7d234b86a503ef5497"
+        );
+        assert_eq!(
+            signer.sign_chunk(&[]).unwrap(),
+            "b6c6ea8a5354eaf15b3cb7646744f4275b71ea724fed81ceb9323e279d449df9"
+        );
+    }
+
+    #[test]
+    fn test_streaming_signing_trailer() {
+        let mut headers = streaming_headers(STREAMING_PAYLOAD_TRAILER);
+        headers.insert(
+            HeaderName::from_static("x-amz-trailer"),
+            "x-amz-checksum-crc32c".parse().unwrap(),
+        );
+        let trailer = "x-amz-checksum-crc32c:sOO8/Q==";
+        assert_eq!(
+            aws_chunked_len(66560, 65536, Some(trailer.len() as u64)),
+            66946
+        );
+
+        let (mut signer, seed_signature) =
+            streaming_seed_signature(&headers, STREAMING_PAYLOAD_TRAILER);
+        assert_eq!(
+            seed_signature,
+            "106e2a8a18243abcf37539882f36619c00e2dfc72633413f02d3b74544bfeb8e"
+        );
+        assert_eq!(
+            signer.sign_chunk(&[b'a'; 65536]).unwrap(),
+            "b474d8862b1487a5145d686f57f013e54db672cee1c953b3010fb58501ef5aa2"
+        );
+        assert_eq!(
+            signer.sign_chunk(&[b'a'; 1024]).unwrap(),
+            "1c1344b170168f8e65b41376b44b20fe354e373826ccbbe2c1d40a8cae51e5c7"
+        );
+        assert_eq!(
+            signer.sign_chunk(&[]).unwrap(),
+            "2ca2aba2005185cf7159c6277faf83795951dd77a3a99e6e65d5c9f85863f992"
+        );
+        assert_eq!(
+            signer.sign_trailer(&format!("{}\n", trailer)).unwrap(),
+            "d81f82fc3505edab99d459891051a732e8730629a2e4a59689829ca17fe2e435"
+        );
+    }
+
     #[test]
     fn test_parse_list_bucket_result() {
         let result_string = r###"
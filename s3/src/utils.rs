@@ -1,13 +1,19 @@
 use std::str::FromStr;
 
-use crate::error::S3Error;
+use crate::error::{S3Error, ServiceError};
 use crate::request::ResponseData;
+use crate::serde_types::AwsError;
 use crate::{bucket::CHUNK_SIZE, serde_types::HeadObjectResult};
 
 use crate::request::{AsyncRead, AsyncReadExt};
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use time::{OffsetDateTime, UtcOffset};
+
+const HTTP_DATE: &[time::format_description::FormatItem<'static>] = time::macros::format_description!(
+    "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+);
 
 pub struct PutStreamResponse {
     status_code: u16,
@@ -61,6 +67,11 @@ pub fn etag_for_path(path: impl AsRef<Path>) -> Result<String, S3Error> {
     Ok(etag)
 }
 
+/// Format `date` as an HTTP date, e.g. `Tue, 02 Jan 2024 02:04:05 GMT`.
+pub fn http_date(date: OffsetDateTime) -> Result<String, S3Error> {
+    Ok(date.to_offset(UtcOffset::UTC).format(HTTP_DATE)?)
+}
+
 pub fn read_chunk<R: Read>(reader: &mut R) -> Result<Vec<u8>, S3Error> {
     let mut chunk = Vec::with_capacity(CHUNK_SIZE);
     let mut take = reader.take(CHUNK_SIZE as u64);
@@ -70,8 +81,16 @@ pub fn read_chunk<R: Read>(reader: &mut R) -> Result<Vec<u8>, S3Error> {
 }
 
 pub async fn read_chunk_async<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, S3Error> {
-    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
-    let mut take = reader.take(CHUNK_SIZE as u64);
+    read_chunk_sized_async(reader, CHUNK_SIZE).await
+}
+
+/// Read up to `size` bytes, less only if the reader is exhausted.
+pub async fn read_chunk_sized_async<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    size: usize,
+) -> Result<Vec<u8>, S3Error> {
+    let mut chunk = Vec::with_capacity(size);
+    let mut take = reader.take(size as u64);
     take.read_to_end(&mut chunk).await?;
 
     Ok(chunk)
@@ -144,15 +163,44 @@ impl From<&http::HeaderMap> for HeadObjectResult {
 
 pub(crate) fn error_from_response_data(response_data: ResponseData) -> Result<S3Error, S3Error> {
     let utf8_content = String::from_utf8(response_data.as_slice().to_vec())?;
-    Err(S3Error::HttpFailWithBody(
-        response_data.status_code(),
-        utf8_content,
-    ))
+    Err(error_from_body(response_data.status_code(), utf8_content))
+}
+
+/// Turn a failed response into an [`S3Error::Service`] if the body is an S3
+/// error document or empty, and into [`S3Error::HttpFailWithBody`] otherwise.
+pub(crate) fn error_from_body(status: u16, body: String) -> S3Error {
+    if body.trim().is_empty() {
+        let reason = http::StatusCode::from_u16(status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default();
+        return S3Error::Service(Box::new(ServiceError {
+            status,
+            code: reason.replace(' ', ""),
+            message: reason.to_string(),
+            request_id: None,
+            host_id: None,
+            resource: None,
+        }));
+    }
+
+    match quick_xml::de::from_str::<AwsError>(&body) {
+        Ok(error) => S3Error::Service(Box::new(ServiceError {
+            status,
+            code: error.code,
+            message: error.message,
+            request_id: Some(error.request_id).filter(|id| !id.is_empty()),
+            host_id: error.host_id,
+            resource: error.resource,
+        })),
+        Err(_) => S3Error::HttpFailWithBody(status, body),
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::utils::etag_for_path;
+    use crate::error::S3Error;
+    use crate::utils::{error_from_body, etag_for_path};
     use std::fs::File;
     use std::io::prelude::*;
     use std::io::Cursor;
@@ -214,4 +262,66 @@ mod test {
         let result = super::read_chunk(&mut blob).unwrap();
         assert_eq!(result.len(), 1_611_392);
     }
+
+    #[test]
+    fn test_error_from_body_parses_service_error() {
+        let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Error>
+                <Code>NoSuchKey</Code>
+                <Message>The resource you requested does not exist</Message>
+                <Resource>/mybucket/myfoto.jpg</Resource>
+                <RequestId>4442587FB7D0A2F9</RequestId>
+                <HostId>aGVsbG8=</HostId>
+            </Error>"#;
+
+        let error = error_from_body(404, body.to_string());
+        assert!(error.is_not_found());
+        assert!(!error.is_access_denied());
+        assert_eq!(error.code(), Some("NoSuchKey"));
+
+        let service = error.as_service_error().unwrap();
+        assert_eq!(service.status, 404);
+        assert_eq!(service.message, "The resource you requested does not exist");
+        assert_eq!(service.resource.as_deref(), Some("/mybucket/myfoto.jpg"));
+        assert_eq!(service.request_id.as_deref(), Some("4442587FB7D0A2F9"));
+        assert_eq!(service.host_id.as_deref(), Some("aGVsbG8="));
+    }
+
+    #[test]
+    fn test_error_from_body_without_body() {
+        let error = error_from_body(412, String::new());
+        assert!(error.is_precondition_failed());
+        assert_eq!(error.code(), Some("PreconditionFailed"));
+
+        let error = error_from_body(403, String::new());
+        assert!(error.is_access_denied());
+    }
+
+    #[test]
+    fn test_error_from_body_access_denied_by_code() {
+        let error = |code: &str| {
+            error_from_body(
+                403,
+                format!(
+                    "<Error><Code>{}</Code><Message>message</Message>\
+                     <RequestId>4442587FB7D0A2F9</RequestId></Error>",
+                    code
+                ),
+            )
+        };
+        assert!(error("AccessDenied").is_access_denied());
+        for code in [
+            "SignatureDoesNotMatch",
+            "InvalidAccessKeyId",
+            "RequestTimeTooSkewed",
+        ] {
+            assert!(!error(code).is_access_denied(), "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_error_from_body_keeps_unknown_bodies() {
+        let error = error_from_body(502, "<html>Bad Gateway</html>".to_string());
+        assert!(matches!(error, S3Error::HttpFailWithBody(502, _)));
+    }
 }